@@ -0,0 +1,85 @@
+//! Parse and render throughput benchmarks.
+//!
+//! Run with `cargo bench --bench parse_and_render`. Each benchmark reports the
+//! number of input bytes processed per second.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use tynm::{TypeName, TypeParamsFmtOpts};
+
+/// Duration to run each benchmark for.
+const BENCH_DURATION: Duration = Duration::from_secs(1);
+
+/// Type name strings in the form returned by `core::any::type_name`.
+const TYPE_NAMES: &[&str] = &[
+    "usize",
+    "core::option::Option<alloc::string::String>",
+    "std::collections::hash::map::HashMap<alloc::string::String, alloc::vec::Vec<u8>>",
+    "core::result::Result<alloc::vec::Vec<(u8, &'_ str)>, alloc::boxed::Box<dyn \
+     core::error::Error + core::marker::Send + core::marker::Sync>>",
+    "core::iter::adapters::map::Map<core::iter::adapters::filter::Filter<\
+     core::iter::adapters::zip::Zip<core::slice::iter::Iter<'_, u8>, \
+     alloc::vec::into_iter::IntoIter<u16>>, my_crate::filter::{{closure}}>, \
+     my_crate::map::{{closure}}>",
+    "alloc::boxed::Box<dyn core::ops::function::Fn(&'_ str) -> [u8; 3] + core::marker::Send>",
+    "<alloc::vec::Vec<u8> as core::iter::traits::collect::IntoIterator>::IntoIter",
+    "for<'a> unsafe extern \"C\" fn(&'a u8, *const [my_crate::Node; 4]) -> &'a u8",
+];
+
+fn main() {
+    let total_len = TYPE_NAMES
+        .iter()
+        .map(|type_name| type_name.len())
+        .sum::<usize>();
+
+    bench("parse", total_len, || {
+        TYPE_NAMES.iter().for_each(|type_name| {
+            black_box(TypeName::parse_strict(black_box(type_name)).ok());
+        });
+    });
+
+    let type_names = TYPE_NAMES
+        .iter()
+        .map(|type_name| TypeName::from(*type_name))
+        .collect::<Vec<_>>();
+    bench("render", total_len, || {
+        type_names.iter().for_each(|type_name| {
+            black_box(type_name.as_str_mn_opts(1, 1, TypeParamsFmtOpts::All));
+        });
+    });
+
+    bench("parse_and_render", total_len, || {
+        TYPE_NAMES.iter().for_each(|type_name| {
+            black_box(TypeName::from(black_box(*type_name)).as_str());
+        });
+    });
+}
+
+/// Runs `f` repeatedly for `BENCH_DURATION`, and prints the throughput.
+///
+/// # Parameters
+///
+/// * `name`: Name of the benchmark.
+/// * `bytes_per_iteration`: Number of input bytes that `f` processes.
+/// * `f`: Function to benchmark.
+fn bench(name: &str, bytes_per_iteration: usize, mut f: impl FnMut()) {
+    // Warm up.
+    (0..100).for_each(|_| f());
+
+    let start = Instant::now();
+    let mut iterations = 0u64;
+    while start.elapsed() < BENCH_DURATION {
+        f();
+        iterations += 1;
+    }
+    let elapsed = start.elapsed();
+
+    let bytes = bytes_per_iteration as f64 * iterations as f64;
+    let mib_per_second = bytes / elapsed.as_secs_f64() / (1024.0 * 1024.0);
+    let nanos_per_iteration = elapsed.as_nanos() / u128::from(iterations);
+
+    println!("{name:<20} {mib_per_second:>10.2} MiB/s {nanos_per_iteration:>10} ns/iter");
+}
@@ -0,0 +1,218 @@
+use core::fmt::{self, Error, Write};
+
+/// Fixed-size buffer that type names can be written to without allocating.
+///
+/// When a write does not fit, as much of the text as possible is kept, and the
+/// truncation marker is appended. Text is cut on a `char` boundary, so up to 3
+/// bytes of the buffer may be left unused. Further writes are ignored. Writes
+/// never return an error, so a type name can always be written, even if only
+/// partially.
+///
+/// # Example
+///
+/// ```rust
+/// use tynm::BoundedBuffer;
+///
+/// let mut bytes = [0u8; 12];
+/// let mut buffer = BoundedBuffer::new(&mut bytes);
+///
+/// tynm::write_type_name::<Option<Option<String>>>(&mut buffer).unwrap();
+///
+/// assert_eq!(buffer.as_str(), "Option<Op...");
+/// assert!(buffer.is_truncated());
+/// ```
+pub struct BoundedBuffer<'b> {
+    /// Backing bytes.
+    bytes: &'b mut [u8],
+    /// Number of bytes written.
+    len: usize,
+    /// Text appended when a write does not fit.
+    truncation_marker: &'b str,
+    /// Whether a write did not fit.
+    truncated: bool,
+}
+
+impl<'b> BoundedBuffer<'b> {
+    /// Returns a new `BoundedBuffer` that writes to the given bytes, using
+    /// `"..."` as the truncation marker.
+    pub fn new(bytes: &'b mut [u8]) -> Self {
+        Self {
+            bytes,
+            len: 0,
+            truncation_marker: "...",
+            truncated: false,
+        }
+    }
+
+    /// Sets the text that is appended when a write does not fit.
+    ///
+    /// If the marker is longer than the buffer, only its leading characters
+    /// that fit are written.
+    pub fn with_truncation_marker(mut self, truncation_marker: &'b str) -> Self {
+        self.truncation_marker = truncation_marker;
+        self
+    }
+
+    /// Returns the text written so far.
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len])
+            .expect("`BoundedBuffer` only stores whole `char`s.")
+    }
+
+    /// Returns the number of bytes written.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether nothing has been written.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns whether a write did not fit, and the text was truncated.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Copies `s` to the end of the written bytes.
+    ///
+    /// The caller must ensure that `s` fits.
+    fn push_str(&mut self, s: &str) {
+        self.bytes[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+        self.len += s.len();
+    }
+
+    /// Returns whether `index` is on a `char` boundary of the written bytes.
+    fn is_char_boundary(&self, index: usize) -> bool {
+        index == 0 || index >= self.len || (self.bytes[index] & 0b1100_0000) != 0b1000_0000
+    }
+}
+
+impl Write for BoundedBuffer<'_> {
+    fn write_str(&mut self, s: &str) -> Result<(), Error> {
+        if self.truncated {
+            return Ok(());
+        }
+
+        let capacity = self.bytes.len();
+        if s.len() <= capacity - self.len {
+            self.push_str(s);
+            return Ok(());
+        }
+
+        self.truncated = true;
+
+        // Keep as much text as leaves room for the marker, cutting on a `char`
+        // boundary.
+        let text_capacity = capacity.saturating_sub(self.truncation_marker.len());
+        if self.len > text_capacity {
+            let mut len = text_capacity;
+            while !self.is_char_boundary(len) {
+                len -= 1;
+            }
+            self.len = len;
+        } else {
+            self.push_str(floor_char_boundary(s, text_capacity - self.len));
+        }
+
+        let truncation_marker = floor_char_boundary(self.truncation_marker, capacity - self.len);
+        self.push_str(truncation_marker);
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for BoundedBuffer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundedBuffer")
+            .field("text", &self.as_str())
+            .field("capacity", &self.bytes.len())
+            .field("truncated", &self.truncated)
+            .finish()
+    }
+}
+
+/// Returns the longest prefix of `s` that is at most `len` bytes, and ends on a
+/// `char` boundary.
+fn floor_char_boundary(s: &str, len: usize) -> &str {
+    if len >= s.len() {
+        return s;
+    }
+
+    let mut end = len;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    &s[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use super::BoundedBuffer;
+
+    #[test]
+    fn writes_text_that_fits() {
+        let mut bytes = [0u8; 8];
+        let mut buffer = BoundedBuffer::new(&mut bytes);
+
+        buffer.write_str("Vec<").unwrap();
+        buffer.write_str("u8>").unwrap();
+
+        assert_eq!("Vec<u8>", buffer.as_str());
+        assert!(!buffer.is_truncated());
+    }
+
+    #[test]
+    fn truncates_text_with_marker() {
+        let mut bytes = [0u8; 8];
+        let mut buffer = BoundedBuffer::new(&mut bytes).with_truncation_marker("~");
+
+        buffer.write_str("Option<").unwrap();
+        buffer.write_str("String>").unwrap();
+        buffer.write_str("ignored").unwrap();
+
+        assert_eq!("Option<~", buffer.as_str());
+        assert!(buffer.is_truncated());
+    }
+
+    #[test]
+    fn truncates_previously_written_text_on_char_boundary() {
+        let mut bytes = [0u8; 6];
+        let mut buffer = BoundedBuffer::new(&mut bytes);
+
+        buffer.write_str("ab\u{e9}c").unwrap();
+        buffer.write_str("de").unwrap();
+
+        // `"é"` is two bytes, and is dropped whole to leave room for `"..."`.
+        assert_eq!("ab...", buffer.as_str());
+        assert!(buffer.is_truncated());
+    }
+
+    #[test]
+    fn truncates_written_text_within_multi_byte_char() {
+        let mut bytes = [0u8; 7];
+        let mut buffer = BoundedBuffer::new(&mut bytes).with_truncation_marker("\u{2026}");
+
+        buffer.write_str("ab").unwrap();
+        buffer.write_str("\u{20ac}\u{20ac}").unwrap();
+
+        // Only 2 bytes of the first 3 byte `"€"` fit before the 3 byte marker,
+        // so it is dropped whole, leaving 2 bytes unused.
+        assert_eq!("ab\u{2026}", buffer.as_str());
+        assert_eq!(5, buffer.len());
+        assert!(buffer.is_truncated());
+    }
+
+    #[test]
+    fn truncates_marker_longer_than_buffer() {
+        let mut bytes = [0u8; 2];
+        let mut buffer = BoundedBuffer::new(&mut bytes);
+
+        buffer.write_str("Option").unwrap();
+
+        assert_eq!("..", buffer.as_str());
+    }
+}
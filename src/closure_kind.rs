@@ -0,0 +1,62 @@
+/// Kind of closure or coroutine.
+///
+/// `core::any::type_name` names closures, `async` blocks, the futures of
+/// `async fn`s, and coroutines alike with a `{{closure}}` segment, e.g.
+/// `my_crate::main::{{closure}}`, so these are all parsed as
+/// [`ClosureKind::Closure`]. The other kinds are only returned for the
+/// segments that rustc writes in diagnostics and symbol names, such as
+/// `{async block@src/main.rs:3:5}`, when those are parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClosureKind {
+    /// A closure, or any other closure-like type named by `type_name`, e.g.
+    /// `my_crate::spawn_worker::{{closure}}` or `my_crate::main::{closure#0}`.
+    Closure,
+    /// An `async` block named by its diagnostic segment, e.g. `{async
+    /// block@src/main.rs:3:5}`.
+    AsyncBlock,
+    /// The body of an `async fn` named by its diagnostic segment, e.g.
+    /// `{async fn body of my_crate::run()}`.
+    AsyncFnBody,
+    /// A coroutine or `gen` block named by its diagnostic segment, e.g.
+    /// `{coroutine#0}`.
+    Coroutine,
+}
+
+impl ClosureKind {
+    /// Returns the short description of this kind, e.g. `"async block"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Closure => "closure",
+            Self::AsyncBlock => "async block",
+            Self::AsyncFnBody => "async fn",
+            Self::Coroutine => "coroutine",
+        }
+    }
+}
+
+/// Returns the kind of closure or coroutine that a `{..}` path segment
+/// represents, if any.
+pub(crate) fn closure_kind(segment: &str) -> Option<ClosureKind> {
+    // `{{closure}}` is wrapped in two sets of braces, `{closure#0}` in one.
+    let inner = segment.strip_prefix('{')?.strip_suffix('}')?;
+    let inner = inner
+        .strip_prefix('{')
+        .and_then(|inner| inner.strip_suffix('}'))
+        .unwrap_or(inner);
+
+    if inner.starts_with("async fn body") {
+        Some(ClosureKind::AsyncFnBody)
+    } else if inner.starts_with("async block") || inner.starts_with("async_block") {
+        Some(ClosureKind::AsyncBlock)
+    } else if inner.starts_with("closure") || inner.starts_with("async closure") {
+        Some(ClosureKind::Closure)
+    } else if inner.starts_with("coroutine")
+        || inner.starts_with("static coroutine")
+        || inner.starts_with("gen block")
+        || inner.starts_with("async gen block")
+    {
+        Some(ClosureKind::Coroutine)
+    } else {
+        None
+    }
+}
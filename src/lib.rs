@@ -15,6 +15,7 @@
 //! In code:
 //!
 //! ```rust
+//! # #[cfg(feature = "alloc")]
 //! # fn main() {
 //! #[rustfmt::skip]
 //! assert_eq!(
@@ -36,11 +37,52 @@
 //!     .iter()
 //!     .for_each(|(left, right)| assert_eq!(left, right));
 //! # }
+//! #
+//! # #[cfg(not(feature = "alloc"))]
+//! # fn main() {}
 //!
 //! # #[rustfmt::skip]
 //! # mod rust_out { pub mod two { pub mod three { pub struct Struct; } } }
 //! ```
 //!
+//! To combine formatting options, build a [`TypeNameFmt`] and pass it to
+//! [`type_name_with`] or [`TypeName::display`]:
+//!
+//! ```rust
+//! # #[cfg(feature = "alloc")]
+//! # fn main() {
+//! use tynm::TypeNameFmt;
+//!
+//! let fmt = TypeNameFmt::new()
+//!     .segment_count_left(1)
+//!     .elide_auto_traits(true);
+//!
+//! assert_eq!(
+//!     tynm::type_name_with::<Box<dyn core::error::Error + Send + Sync>>(&fmt),
+//!     "alloc::..::Box<dyn core::..::Error>",
+//! );
+//! # }
+//! #
+//! # #[cfg(not(feature = "alloc"))]
+//! # fn main() {}
+//! ```
+//!
+//! If a type name cannot be fully parsed, the `type_name*` functions return it
+//! unshortened rather than risk printing a wrong name. Use the corresponding
+//! `try_type_name*` function to receive a [`ParseError`] instead.
+//!
+//! # Without `alloc`
+//!
+//! The `alloc` feature is enabled by default. With `default-features = false`,
+//! only [`write_type_name`], [`write_type_namemn_opts`], and
+//! [`write_type_name_with`] are available. These write the shortened type name
+//! directly into any [`core::fmt::Write`], such as a [`BoundedBuffer`], without
+//! allocating.
+//!
+//! ```toml
+//! tynm = { version = "0.2.0", default-features = false }
+//! ```
+//!
 //! # Motivation
 //!
 //! The [`core::any::type_name`] function stabilized in Rust 1.38 returns the
@@ -52,20 +94,57 @@
 //!
 //! [`core::any::type_name`]: https://doc.rust-lang.org/std/any/fn.type_name.html
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::fmt::{Error, Write};
+
+#[cfg(feature = "alloc")]
 use alloc::string::String;
 
 pub use crate::{
+    bounded_buffer::BoundedBuffer, type_name_fmt::TypeNameFmt,
     type_params_fmt_opts::TypeParamsFmtOpts,
-    types::{TypeName, TypeNameDisplay},
+};
+
+#[cfg(feature = "alloc")]
+pub use crate::{
+    closure_kind::ClosureKind,
+    parse_error::{ParseError, ParseErrorKind},
+    parse_limits::ParseLimits,
+    type_name_cache::TypeNameCache,
+    type_name_flat::{
+        TypeNameFlat, TypeNameFlatKind, TypeNameFlatNode, TypeNameFlatNodes, TypeNameFlatSegments,
+    },
+    types::{GenericArg, TypeName, TypeNameDisplay, TypeNameDisplayOwned},
 };
 
 #[cfg(feature = "info")]
 pub use crate::type_name_info::TypeNameInfo;
 
-mod parser;
+mod bounded_buffer;
+mod closure_kind;
+mod std_paths;
+mod stream;
+mod type_name_fmt;
 mod type_params_fmt_opts;
+
+#[cfg(feature = "alloc")]
+mod parse_error;
+#[cfg(feature = "alloc")]
+mod parse_limits;
+#[cfg(feature = "alloc")]
+mod parser;
+#[cfg(feature = "alloc")]
+mod render;
+#[cfg(feature = "alloc")]
+mod type_name_cache;
+#[cfg(feature = "alloc")]
+mod type_name_flat;
+#[cfg(feature = "alloc")]
 mod types;
 
 #[cfg(feature = "info")]
@@ -82,6 +161,7 @@ mod type_name_info;
 /// ```rust
 /// assert_eq!(tynm::type_name::<Option<String>>(), "Option<String>",);
 /// ```
+#[cfg(feature = "alloc")]
 pub fn type_name<T>() -> String
 where
     T: ?Sized,
@@ -121,6 +201,7 @@ where
 /// );
 /// # }
 /// ```
+#[cfg(feature = "alloc")]
 pub fn type_name_opts<T>(type_params_fmt_opts: TypeParamsFmtOpts) -> String
 where
     T: ?Sized,
@@ -147,6 +228,7 @@ where
 ///     "core::..::Option<alloc::..::String>",
 /// );
 /// ```
+#[cfg(feature = "alloc")]
 pub fn type_namem<T>(m: usize) -> String
 where
     T: ?Sized,
@@ -191,6 +273,7 @@ where
 /// );
 /// # }
 /// ```
+#[cfg(feature = "alloc")]
 pub fn type_namem_opts<T>(m: usize, type_params_fmt_opts: TypeParamsFmtOpts) -> String
 where
     T: ?Sized,
@@ -217,6 +300,7 @@ where
 ///     "..::option::Option<..::string::String>",
 /// );
 /// ```
+#[cfg(feature = "alloc")]
 pub fn type_namen<T>(n: usize) -> String
 where
     T: ?Sized,
@@ -257,6 +341,7 @@ where
 /// );
 /// # }
 /// ```
+#[cfg(feature = "alloc")]
 pub fn type_namen_opts<T>(n: usize, type_params_fmt_opts: TypeParamsFmtOpts) -> String
 where
     T: ?Sized,
@@ -284,6 +369,7 @@ where
 ///     "..::option::Option<..::string::String>",
 /// );
 /// ```
+#[cfg(feature = "alloc")]
 pub fn type_namemn<T>(m: usize, n: usize) -> String
 where
     T: ?Sized,
@@ -325,95 +411,781 @@ where
 /// );
 /// # }
 /// ```
+#[cfg(feature = "alloc")]
 pub fn type_namemn_opts<T>(m: usize, n: usize, type_params_fmt_opts: TypeParamsFmtOpts) -> String
 where
     T: ?Sized,
 {
-    let type_name_qualified = core::any::type_name::<T>();
-
-    let type_name = TypeName::from(type_name_qualified);
-    type_name.as_str_mn_opts(m, n, type_params_fmt_opts)
+    type_name_with::<T>(&TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
 }
 
-#[cfg(test)]
-mod tests {
-    use alloc::{boxed::Box, format, string::String, vec::Vec};
-
-    use super::{TypeName, TypeParamsFmtOpts};
-    use crate as tynm;
+/// Returns the type name formatted using the given options.
+///
+/// # Parameters
+///
+/// * `fmt`: How to format the type name, see the type documentation for
+///   details.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose type name should be returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use tynm::{TypeNameFmt, TypeParamsFmtOpts};
+///
+/// pub mod a {
+///     pub struct MyStruct<T>(T);
+/// }
+///
+/// # fn main() {
+/// # use crate::a::MyStruct;
+/// let fmt = TypeNameFmt::new()
+///     .segment_count_right(1)
+///     .type_params_fmt_opts(TypeParamsFmtOpts::Std);
+///
+/// assert_eq!(
+///     tynm::type_name_with::<Vec<MyStruct<String>>>(&fmt),
+///     "..::vec::Vec<..::a::MyStruct>",
+/// );
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub fn type_name_with<T>(fmt: &TypeNameFmt) -> String
+where
+    T: ?Sized,
+{
+    let type_name_qualified = core::any::type_name::<T>();
 
-    #[test]
-    fn type_name_primitives() {
-        assert_eq!(tynm::type_name::<usize>(), "usize");
-        assert_eq!(tynm::type_name::<u8>(), "u8");
-        assert_eq!(tynm::type_name::<u16>(), "u16");
-        assert_eq!(tynm::type_name::<u32>(), "u32");
-        assert_eq!(tynm::type_name::<u64>(), "u64");
-        assert_eq!(tynm::type_name::<u128>(), "u128");
+    type_name_str_with(type_name_qualified, fmt)
+}
 
-        assert_eq!(tynm::type_name::<isize>(), "isize");
-        assert_eq!(tynm::type_name::<i8>(), "i8");
-        assert_eq!(tynm::type_name::<i16>(), "i16");
-        assert_eq!(tynm::type_name::<i32>(), "i32");
-        assert_eq!(tynm::type_name::<i64>(), "i64");
-        assert_eq!(tynm::type_name::<i128>(), "i128");
+/// Returns the simple type name as a `&'static str`.
+///
+/// The type name is computed once and cached, so this is cheap to call
+/// repeatedly, e.g. when logging on a hot path.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose simple type name should be returned.
+///
+/// # Examples
+///
+/// ```rust
+/// let type_name: &'static str = tynm::type_name_static::<Option<String>>();
+///
+/// assert_eq!(type_name, "Option<String>");
+/// ```
+#[cfg(feature = "std")]
+pub fn type_name_static<T>() -> &'static str
+where
+    T: ?Sized + 'static,
+{
+    type_namemn_opts_static::<T>(0, 0, TypeParamsFmtOpts::All)
+}
 
-        assert_eq!(tynm::type_name::<f32>(), "f32");
-        assert_eq!(tynm::type_name::<f64>(), "f64");
+/// Returns the type name with `m` most significant, and `n` least significant
+/// module path segments as a `&'static str`.
+///
+/// The type name is computed once for each combination of `T` and options,
+/// and cached.
+///
+/// # Parameters
+///
+/// * `m`: Number of most significant module path segments to include.
+/// * `n`: Number of least significant module path segments to include.
+/// * `type_params_fmt_opts`: How to format type parameters, see the type
+///   documentation for details.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose simple type name should be returned.
+#[cfg(feature = "std")]
+pub fn type_namemn_opts_static<T>(
+    m: usize,
+    n: usize,
+    type_params_fmt_opts: TypeParamsFmtOpts,
+) -> &'static str
+where
+    T: ?Sized + 'static,
+{
+    type_name_with_static::<T>(&TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+}
 
-        assert_eq!(tynm::type_name::<bool>(), "bool");
-        assert_eq!(tynm::type_name::<char>(), "char");
+/// Returns the type name formatted with the given options as a `&'static
+/// str`.
+///
+/// The type name is computed once for each combination of `T` and options,
+/// and cached. Cached type names are looked up by the type's `TypeId`, and
+/// each thread keeps the type names it has read, so repeated calls do not
+/// take a lock.
+///
+/// Options set with [`TypeNameFmt::std_crates_fn`] are compared by function
+/// pointer address. The same function may have more than one address, so
+/// each distinct address gets its own cached, leaked type name.
+///
+/// # Parameters
+///
+/// * `fmt`: Options for formatting the type name.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose type name should be returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use tynm::TypeNameFmt;
+///
+/// let fmt = TypeNameFmt::new()
+///     .public_std_paths(true)
+///     .segment_count_left(1);
+/// let type_name: &'static str = tynm::type_name_with_static::<Option<String>>(&fmt);
+///
+/// assert_eq!(type_name, "std::..::Option<std::..::String>");
+/// ```
+#[cfg(feature = "std")]
+pub fn type_name_with_static<T>(fmt: &TypeNameFmt) -> &'static str
+where
+    T: ?Sized + 'static,
+{
+    type_name_cache::type_name_with_static::<T>(fmt)
+}
 
-        assert_eq!(
-            tynm::type_name::<Vec<(u32, String)>>(),
-            "Vec<(u32, String)>"
-        );
+/// Returns the formatted type name, or the original string if it cannot be
+/// fully parsed.
+#[cfg(feature = "alloc")]
+fn type_name_str_with(type_name_qualified: &str, fmt: &TypeNameFmt) -> String {
+    match TypeName::parse_strict(type_name_qualified) {
+        Ok(type_name) => type_name.as_str_with(fmt),
+        Err(_) => String::from(type_name_qualified),
     }
+}
 
-    #[test]
-    fn type_name_array() {
-        assert_eq!(tynm::type_name::<[u32; 3]>(), "[u32; 3]");
-        assert_eq!(
-            tynm::type_name::<[Option<String>; 3]>(),
-            "[Option<String>; 3]"
-        );
-    }
+/// Returns the simple type name, or an error if it cannot be fully parsed.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose simple type name should be returned.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(
+///     tynm::try_type_name::<Option<String>>(),
+///     Ok(String::from("Option<String>"))
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn try_type_name<T>() -> Result<String, ParseError>
+where
+    T: ?Sized,
+{
+    try_type_namemn::<T>(0, 0)
+}
 
-    #[test]
-    fn type_name_opts() {
-        struct MyStruct<T>(T);
+/// Returns the simple type name, or an error if it cannot be fully parsed.
+///
+/// # Parameters
+///
+/// * `type_params_fmt_opts`: How to format type parameters, see the type
+///   documentation for details.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose simple type name should be returned.
+///
+/// # Examples
+///
+/// ```rust
+/// # use tynm::TypeParamsFmtOpts;
+/// assert_eq!(
+///     tynm::try_type_name_opts::<Option<String>>(TypeParamsFmtOpts::Std),
+///     Ok(String::from("Option<String>")),
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn try_type_name_opts<T>(type_params_fmt_opts: TypeParamsFmtOpts) -> Result<String, ParseError>
+where
+    T: ?Sized,
+{
+    try_type_namemn_opts::<T>(0, 0, type_params_fmt_opts)
+}
 
-        assert_eq!(
-            tynm::type_name_opts::<MyStruct<String>>(TypeParamsFmtOpts::All),
-            "MyStruct<String>"
-        );
-        assert_eq!(
-            tynm::type_name_opts::<MyStruct<String>>(TypeParamsFmtOpts::Std),
-            "MyStruct",
-        );
-        assert_eq!(
-            tynm::type_name_opts::<Vec<MyStruct<String>>>(TypeParamsFmtOpts::Std),
-            "Vec<MyStruct>",
-        );
-    }
+/// Returns the type name with at most `m` most significant module path
+/// segments, or an error if it cannot be fully parsed.
+///
+/// # Parameters
+///
+/// * `m`: Number of most significant module path segments to include.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose simple type name should be returned.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(
+///     tynm::try_type_namem::<Option<String>>(1),
+///     Ok(String::from("core::..::Option<alloc::..::String>")),
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn try_type_namem<T>(m: usize) -> Result<String, ParseError>
+where
+    T: ?Sized,
+{
+    try_type_namemn::<T>(m, 0)
+}
 
-    #[test]
-    fn type_namem_opts() {
-        struct MyStruct<T>(T);
+/// Returns the type name with at most `m` most significant module path
+/// segments, or an error if it cannot be fully parsed.
+///
+/// # Parameters
+///
+/// * `m`: Number of most significant module path segments to include.
+/// * `type_params_fmt_opts`: How to format type parameters, see the type
+///   documentation for details.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose simple type name should be returned.
+#[cfg(feature = "alloc")]
+pub fn try_type_namem_opts<T>(
+    m: usize,
+    type_params_fmt_opts: TypeParamsFmtOpts,
+) -> Result<String, ParseError>
+where
+    T: ?Sized,
+{
+    try_type_namemn_opts::<T>(m, 0, type_params_fmt_opts)
+}
 
-        assert_eq!(
-            tynm::type_namem_opts::<MyStruct<String>>(1, TypeParamsFmtOpts::All),
-            "tynm::..::MyStruct<alloc::..::String>",
-        );
-        assert_eq!(
-            tynm::type_namem_opts::<MyStruct<String>>(1, TypeParamsFmtOpts::Std),
-            "tynm::..::MyStruct",
-        );
-    }
+/// Returns the type name with at most `n` least significant module path
+/// segments, or an error if it cannot be fully parsed.
+///
+/// # Parameters
+///
+/// * `n`: Number of least significant module path segments to include.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose simple type name should be returned.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(
+///     tynm::try_type_namen::<Option<String>>(1),
+///     Ok(String::from("..::option::Option<..::string::String>")),
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn try_type_namen<T>(n: usize) -> Result<String, ParseError>
+where
+    T: ?Sized,
+{
+    try_type_namemn::<T>(0, n)
+}
 
-    #[test]
-    fn type_namemn_opts() {
-        struct MyStruct<T>(T);
+/// Returns the type name with at most `n` least significant module path
+/// segments, or an error if it cannot be fully parsed.
+///
+/// # Parameters
+///
+/// * `n`: Number of least significant module path segments to include.
+/// * `type_params_fmt_opts`: How to format type parameters, see the type
+///   documentation for details.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose simple type name should be returned.
+#[cfg(feature = "alloc")]
+pub fn try_type_namen_opts<T>(
+    n: usize,
+    type_params_fmt_opts: TypeParamsFmtOpts,
+) -> Result<String, ParseError>
+where
+    T: ?Sized,
+{
+    try_type_namemn_opts::<T>(0, n, type_params_fmt_opts)
+}
+
+/// Returns the type name with `m` most significant, and `n` least significant
+/// module path segments, or an error if it cannot be fully parsed.
+///
+/// # Parameters
+///
+/// * `m`: Number of most significant module path segments to include.
+/// * `n`: Number of least significant module path segments to include.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose simple type name should be returned.
+#[cfg(feature = "alloc")]
+pub fn try_type_namemn<T>(m: usize, n: usize) -> Result<String, ParseError>
+where
+    T: ?Sized,
+{
+    try_type_namemn_opts::<T>(m, n, TypeParamsFmtOpts::All)
+}
+
+/// Returns the type name with `m` most significant, and `n` least significant
+/// module path segments, or an error if it cannot be fully parsed.
+///
+/// # Parameters
+///
+/// * `m`: Number of most significant module path segments to include.
+/// * `n`: Number of least significant module path segments to include.
+/// * `type_params_fmt_opts`: How to format type parameters, see the type
+///   documentation for details.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose simple type name should be returned.
+#[cfg(feature = "alloc")]
+pub fn try_type_namemn_opts<T>(
+    m: usize,
+    n: usize,
+    type_params_fmt_opts: TypeParamsFmtOpts,
+) -> Result<String, ParseError>
+where
+    T: ?Sized,
+{
+    try_type_name_with::<T>(&TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+}
+
+/// Returns the type name formatted using the given options, or an error if it
+/// cannot be fully parsed.
+///
+/// # Parameters
+///
+/// * `fmt`: How to format the type name, see the type documentation for
+///   details.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose type name should be returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use tynm::TypeNameFmt;
+///
+/// assert_eq!(
+///     tynm::try_type_name_with::<Option<String>>(&TypeNameFmt::new().segment_count_left(1)),
+///     Ok(String::from("core::..::Option<alloc::..::String>")),
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn try_type_name_with<T>(fmt: &TypeNameFmt) -> Result<String, ParseError>
+where
+    T: ?Sized,
+{
+    let type_name_qualified = core::any::type_name::<T>();
+
+    let type_name = TypeName::parse_strict(type_name_qualified)?;
+    Ok(type_name.as_str_with(fmt))
+}
+
+/// Writes the simple type name to the given buffer, without allocating.
+///
+/// Unlike [`type_name`], this does not parse the type name, and is available
+/// without the `alloc` feature. See [`write_type_namemn_opts`] for details.
+///
+/// # Parameters
+///
+/// * `buffer`: Buffer to write to.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose simple type name should be written.
+///
+/// # Examples
+///
+/// ```rust
+/// use tynm::BoundedBuffer;
+///
+/// let mut bytes = [0u8; 32];
+/// let mut buffer = BoundedBuffer::new(&mut bytes);
+///
+/// tynm::write_type_name::<Option<String>>(&mut buffer).unwrap();
+///
+/// assert_eq!(buffer.as_str(), "Option<String>");
+/// ```
+pub fn write_type_name<T>(buffer: &mut impl Write) -> Result<(), Error>
+where
+    T: ?Sized,
+{
+    write_type_namemn_opts::<T>(buffer, 0, 0, TypeParamsFmtOpts::All)
+}
+
+/// Writes the type name with `m` most significant, and `n` least significant
+/// module path segments to the given buffer, without allocating.
+///
+/// The `core::any::type_name` string is scanned once, and each path is written
+/// as it is found. This produces the same output as [`type_namemn_opts`] for
+/// type names that can be parsed, including the short form of closures, e.g.
+/// `{closure in main}`.
+///
+/// # Parameters
+///
+/// * `buffer`: Buffer to write to.
+/// * `m`: Number of most significant module path segments to include.
+/// * `n`: Number of least significant module path segments to include.
+/// * `type_params_fmt_opts`: How to format type parameters, see the type
+///   documentation for details.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose simple type name should be written.
+///
+/// # Examples
+///
+/// ```rust
+/// use tynm::{BoundedBuffer, TypeParamsFmtOpts};
+///
+/// let mut bytes = [0u8; 64];
+/// let mut buffer = BoundedBuffer::new(&mut bytes);
+///
+/// tynm::write_type_namemn_opts::<Option<String>>(&mut buffer, 1, 0, TypeParamsFmtOpts::All)
+///     .unwrap();
+///
+/// assert_eq!(buffer.as_str(), "core::..::Option<alloc::..::String>");
+/// ```
+pub fn write_type_namemn_opts<T>(
+    buffer: &mut impl Write,
+    m: usize,
+    n: usize,
+    type_params_fmt_opts: TypeParamsFmtOpts,
+) -> Result<(), Error>
+where
+    T: ?Sized,
+{
+    write_type_name_with::<T>(buffer, &TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+}
+
+/// Writes the type name formatted using the given options to the given buffer,
+/// without allocating.
+///
+/// See [`write_type_namemn_opts`] for how this differs from
+/// [`type_name_with`].
+///
+/// # Parameters
+///
+/// * `buffer`: Buffer to write to.
+/// * `fmt`: How to format the type name, see the type documentation for
+///   details.
+///
+/// # Type Parameters
+///
+/// * `T`: Type whose type name should be written.
+///
+/// # Examples
+///
+/// ```rust
+/// use tynm::{BoundedBuffer, TypeNameFmt};
+///
+/// let mut bytes = [0u8; 64];
+/// let mut buffer = BoundedBuffer::new(&mut bytes);
+///
+/// let fmt = TypeNameFmt::new()
+///     .segment_count_left(1)
+///     .normalize_std_paths(true);
+/// tynm::write_type_name_with::<Option<String>>(&mut buffer, &fmt).unwrap();
+///
+/// assert_eq!(buffer.as_str(), "std::..::Option<std::..::String>");
+/// ```
+pub fn write_type_name_with<T>(buffer: &mut impl Write, fmt: &TypeNameFmt) -> Result<(), Error>
+where
+    T: ?Sized,
+{
+    let type_name_qualified = core::any::type_name::<T>();
+
+    stream::write_type_name_str_with(buffer, type_name_qualified, fmt)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::{boxed::Box, format, string::String, vec::Vec};
+
+    use super::{TypeName, TypeNameFmt, TypeParamsFmtOpts};
+    use crate as tynm;
+
+    #[test]
+    fn type_name_primitives() {
+        assert_eq!(tynm::type_name::<usize>(), "usize");
+        assert_eq!(tynm::type_name::<u8>(), "u8");
+        assert_eq!(tynm::type_name::<u16>(), "u16");
+        assert_eq!(tynm::type_name::<u32>(), "u32");
+        assert_eq!(tynm::type_name::<u64>(), "u64");
+        assert_eq!(tynm::type_name::<u128>(), "u128");
+
+        assert_eq!(tynm::type_name::<isize>(), "isize");
+        assert_eq!(tynm::type_name::<i8>(), "i8");
+        assert_eq!(tynm::type_name::<i16>(), "i16");
+        assert_eq!(tynm::type_name::<i32>(), "i32");
+        assert_eq!(tynm::type_name::<i64>(), "i64");
+        assert_eq!(tynm::type_name::<i128>(), "i128");
+
+        assert_eq!(tynm::type_name::<f32>(), "f32");
+        assert_eq!(tynm::type_name::<f64>(), "f64");
+
+        assert_eq!(tynm::type_name::<bool>(), "bool");
+        assert_eq!(tynm::type_name::<char>(), "char");
+
+        assert_eq!(
+            tynm::type_name::<Vec<(u32, String)>>(),
+            "Vec<(u32, String)>"
+        );
+    }
+
+    #[test]
+    fn type_name_array() {
+        assert_eq!(tynm::type_name::<[u32; 3]>(), "[u32; 3]");
+        assert_eq!(
+            tynm::type_name::<[Option<String>; 3]>(),
+            "[Option<String>; 3]"
+        );
+    }
+
+    #[test]
+    fn type_name_opts() {
+        struct MyStruct<T>(T);
+
+        assert_eq!(
+            tynm::type_name_opts::<MyStruct<String>>(TypeParamsFmtOpts::All),
+            "MyStruct<String>"
+        );
+        assert_eq!(
+            tynm::type_name_opts::<MyStruct<String>>(TypeParamsFmtOpts::Std),
+            "MyStruct",
+        );
+        assert_eq!(
+            tynm::type_name_opts::<Vec<MyStruct<String>>>(TypeParamsFmtOpts::Std),
+            "Vec<MyStruct>",
+        );
+    }
+
+    #[test]
+    fn type_name_opts_none() {
+        assert_eq!(
+            tynm::type_name_opts::<Result<(Vec<u8>, u16), String>>(TypeParamsFmtOpts::None),
+            "Result",
+        );
+        assert_eq!(
+            tynm::type_name_opts::<(Vec<u8>, Option<u16>)>(TypeParamsFmtOpts::None),
+            "(Vec, Option)",
+        );
+    }
+
+    #[test]
+    fn type_name_opts_max_depth() {
+        struct Error<T>(T);
+
+        let type_params_fmt_opts = TypeParamsFmtOpts::MaxDepth {
+            max_depth: 1,
+            marker: "..",
+        };
+        assert_eq!(
+            tynm::type_name_opts::<Result<Vec<u8>, Error<String>>>(type_params_fmt_opts),
+            "Result<Vec<..>, Error<..>>",
+        );
+        assert_eq!(
+            tynm::type_name_opts::<Option<(u8,)>>(type_params_fmt_opts),
+            "Option<(..)>",
+        );
+        assert_eq!(
+            tynm::type_name_opts::<Option<&[Box<dyn Fn(Vec<u8>)>]>>(type_params_fmt_opts),
+            "Option<&[Box<..>]>",
+        );
+        assert_eq!(
+            tynm::type_name_opts::<Vec<()>>(TypeParamsFmtOpts::MaxDepth {
+                max_depth: 0,
+                marker: "_",
+            }),
+            "Vec<_>",
+        );
+    }
+
+    #[test]
+    fn type_name_opts_max_params() {
+        struct MyStruct<A, B, C>(A, B, C);
+
+        let type_params_fmt_opts = TypeParamsFmtOpts::MaxParams {
+            max_params: 2,
+            marker: "\u{2026}",
+        };
+        assert_eq!(
+            tynm::type_name_opts::<MyStruct<u8, u16, u32>>(type_params_fmt_opts),
+            "MyStruct<u8, u16, \u{2026}>",
+        );
+        assert_eq!(
+            tynm::type_name_opts::<(u8, (u16,), u32, u64)>(type_params_fmt_opts),
+            "(u8, (u16,), \u{2026})",
+        );
+        assert_eq!(
+            tynm::type_name_opts::<Option<u8>>(TypeParamsFmtOpts::MaxParams {
+                max_params: 0,
+                marker: "..",
+            }),
+            "Option<..>",
+        );
+    }
+
+    #[test]
+    fn type_name_with_std_crates() {
+        struct MyStruct<T>(T);
+
+        let fmt = TypeNameFmt::new()
+            .type_params_fmt_opts(TypeParamsFmtOpts::Std)
+            .std_crates(&["tynm"]);
+        assert_eq!(
+            tynm::type_name_with::<MyStruct<Vec<MyStruct<u8>>>>(&fmt),
+            "MyStruct<Vec>",
+        );
+
+        let fmt = fmt.std_crates_fn(|crate_name| crate_name != "alloc");
+        assert_eq!(
+            tynm::type_name_with::<Option<Vec<MyStruct<u8>>>>(&fmt),
+            "Option<Vec>",
+        );
+    }
+
+    #[test]
+    fn type_name_with_normalize_std_paths() {
+        let fmt = TypeNameFmt::new()
+            .segment_count_left(1)
+            .normalize_std_paths(true);
+        assert_eq!(
+            tynm::type_name_with::<Option<Vec<String>>>(&fmt),
+            "std::..::Option<std::..::Vec<std::..::String>>",
+        );
+
+        let mut buffer = String::new();
+        tynm::write_type_name_with::<Option<Vec<String>>>(&mut buffer, &fmt)
+            .expect("Writing to a `String` never fails.");
+        assert_eq!(buffer, "std::..::Option<std::..::Vec<std::..::String>>");
+    }
+
+    #[test]
+    fn type_name_with_public_std_paths() {
+        fn public_path<T: ?Sized>() -> String {
+            let fmt = TypeNameFmt::new()
+                .segment_count_left(usize::MAX)
+                .type_params_fmt_opts(TypeParamsFmtOpts::None)
+                .public_std_paths(true);
+
+            let mut buffer = String::new();
+            tynm::write_type_name_with::<T>(&mut buffer, &fmt)
+                .expect("Writing to a `String` never fails.");
+            assert_eq!(tynm::type_name_with::<T>(&fmt), buffer);
+
+            buffer
+        }
+
+        use alloc::collections::{btree_map, vec_deque, BTreeMap, VecDeque};
+        use core::{cell, iter, num, ops, ptr, slice, str};
+
+        assert_eq!(public_path::<Vec<u8>>(), "std::vec::Vec");
+        assert_eq!(
+            public_path::<alloc::vec::IntoIter<u8>>(),
+            "std::vec::IntoIter"
+        );
+        assert_eq!(
+            public_path::<BTreeMap<u8, u8>>(),
+            "std::collections::BTreeMap"
+        );
+        assert_eq!(
+            public_path::<btree_map::Entry<'static, u8, u8>>(),
+            "std::collections::btree_map::Entry"
+        );
+        assert_eq!(public_path::<VecDeque<u8>>(), "std::collections::VecDeque");
+        assert_eq!(
+            public_path::<vec_deque::Iter<'static, u8>>(),
+            "std::collections::vec_deque::Iter"
+        );
+        assert_eq!(public_path::<ptr::NonNull<u8>>(), "std::ptr::NonNull");
+        assert_eq!(public_path::<cell::OnceCell<u8>>(), "std::cell::OnceCell");
+        assert_eq!(
+            public_path::<iter::Map<slice::Iter<'static, u8>, fn(&u8) -> u8>>(),
+            "std::iter::Map"
+        );
+        assert_eq!(public_path::<num::NonZero<u8>>(), "std::num::NonZero");
+        assert_eq!(public_path::<ops::Range<u8>>(), "std::ops::Range");
+        assert_eq!(public_path::<str::Chars<'static>>(), "std::str::Chars");
+        assert_eq!(
+            public_path::<dyn iter::Iterator<Item = u8>>(),
+            "dyn std::iter::Iterator"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn type_name_with_public_std_paths_std() {
+        use std::{
+            collections::{hash_map, HashMap, HashSet},
+            io, sync, thread,
+        };
+
+        let fmt = TypeNameFmt::new()
+            .segment_count_left(usize::MAX)
+            .type_params_fmt_opts(TypeParamsFmtOpts::None)
+            .public_std_paths(true);
+
+        assert_eq!(
+            tynm::type_name_with::<HashMap<u8, u8>>(&fmt),
+            "std::collections::HashMap"
+        );
+        assert_eq!(
+            tynm::type_name_with::<HashSet<u8>>(&fmt),
+            "std::collections::HashSet"
+        );
+        assert_eq!(
+            tynm::type_name_with::<hash_map::Entry<'static, u8, u8>>(&fmt),
+            "std::collections::hash_map::Entry"
+        );
+        assert_eq!(
+            tynm::type_name_with::<sync::Mutex<u8>>(&fmt),
+            "std::sync::Mutex"
+        );
+        assert_eq!(
+            tynm::type_name_with::<sync::atomic::AtomicBool>(&fmt),
+            "std::sync::atomic::AtomicBool"
+        );
+        assert_eq!(
+            tynm::type_name_with::<sync::mpsc::Sender<u8>>(&fmt),
+            "std::sync::mpsc::Sender"
+        );
+        assert_eq!(
+            tynm::type_name_with::<io::BufReader<io::Stdin>>(&fmt),
+            "std::io::BufReader"
+        );
+        assert_eq!(
+            tynm::type_name_with::<thread::JoinHandle<u8>>(&fmt),
+            "std::thread::JoinHandle"
+        );
+    }
+
+    #[test]
+    fn type_namem_opts() {
+        struct MyStruct<T>(T);
+
+        assert_eq!(
+            tynm::type_namem_opts::<MyStruct<String>>(1, TypeParamsFmtOpts::All),
+            "tynm::..::MyStruct<alloc::..::String>",
+        );
+        assert_eq!(
+            tynm::type_namem_opts::<MyStruct<String>>(1, TypeParamsFmtOpts::Std),
+            "tynm::..::MyStruct",
+        );
+    }
+
+    #[test]
+    fn type_namemn_opts() {
+        struct MyStruct<T>(T);
 
         assert_eq!(
             tynm::type_namemn_opts::<MyStruct<String>>(1, 1, TypeParamsFmtOpts::Std),
@@ -444,6 +1216,228 @@ mod tests {
         assert_eq!(tynm::type_name::<Option<&String>>(), "Option<&String>");
     }
 
+    #[test]
+    fn type_name_pointer() {
+        assert_eq!(tynm::type_name::<*const u8>(), "*const u8");
+        assert_eq!(tynm::type_name::<*mut String>(), "*mut String");
+        assert_eq!(tynm::type_name::<*const [u8]>(), "*const [u8]");
+        assert_eq!(
+            tynm::type_name::<Option<*mut *const String>>(),
+            "Option<*mut *const String>"
+        );
+        assert_eq!(
+            tynm::type_name::<*mut dyn core::fmt::Debug>(),
+            "*mut dyn Debug"
+        );
+    }
+
+    #[test]
+    fn type_name_pointer_mn() {
+        type T = Option<*mut Vec<*const String>>;
+
+        assert_eq!(
+            tynm::type_namem::<T>(1),
+            "core::..::Option<*mut alloc::..::Vec<*const alloc::..::String>>"
+        );
+        assert_eq!(
+            tynm::type_namen::<T>(1),
+            "..::option::Option<*mut ..::vec::Vec<*const ..::string::String>>"
+        );
+        assert_eq!(
+            tynm::type_namemn::<T>(usize::MAX, usize::MAX),
+            core::any::type_name::<T>()
+        );
+    }
+
+    #[test]
+    fn type_name_pointer_opts() {
+        struct MyStruct<T>(T);
+
+        assert_eq!(
+            tynm::type_name_opts::<*const MyStruct<String>>(TypeParamsFmtOpts::Std),
+            "*const MyStruct"
+        );
+        assert_eq!(
+            tynm::type_name_opts::<Vec<*mut MyStruct<String>>>(TypeParamsFmtOpts::Std),
+            "Vec<*mut MyStruct>"
+        );
+    }
+
+    #[test]
+    fn type_name_function() {
+        assert_eq!(tynm::type_name::<fn()>(), "fn()");
+        assert_eq!(
+            tynm::type_name::<fn(u8, String) -> Option<u8>>(),
+            "fn(u8, String) -> Option<u8>"
+        );
+        assert_eq!(tynm::type_name::<unsafe fn(u8)>(), "unsafe fn(u8)");
+        assert_eq!(
+            tynm::type_name::<extern "C" fn(u8) -> u8>(),
+            "extern \"C\" fn(u8) -> u8"
+        );
+        assert_eq!(
+            tynm::type_name::<unsafe extern "C" fn(u8, ...)>(),
+            "unsafe extern \"C\" fn(u8, ...)"
+        );
+        assert_eq!(
+            tynm::type_name::<fn(&str) -> &str>(),
+            "fn(&'_ str) -> &'_ str"
+        );
+        assert_eq!(tynm::type_name::<fn() -> !>(), "fn() -> !");
+        assert_eq!(
+            tynm::type_name::<fn(fn(u8) -> u8) -> fn()>(),
+            "fn(fn(u8) -> u8) -> fn()"
+        );
+        assert_eq!(
+            tynm::type_name::<core::marker::PhantomData<fn() -> String>>(),
+            "PhantomData<fn() -> String>"
+        );
+    }
+
+    #[test]
+    fn type_name_function_mn() {
+        type T = fn(Vec<String>) -> Option<String>;
+
+        assert_eq!(
+            tynm::type_namem::<T>(1),
+            "fn(alloc::..::Vec<alloc::..::String>) -> core::..::Option<alloc::..::String>"
+        );
+        assert_eq!(
+            tynm::type_namen::<T>(1),
+            "fn(..::vec::Vec<..::string::String>) -> ..::option::Option<..::string::String>"
+        );
+        assert_eq!(
+            tynm::type_namemn::<T>(usize::MAX, usize::MAX),
+            core::any::type_name::<T>()
+        );
+    }
+
+    #[test]
+    fn type_name_function_opts() {
+        struct MyStruct<T>(T);
+
+        assert_eq!(
+            tynm::type_name_opts::<fn(MyStruct<u8>) -> Vec<MyStruct<u8>>>(TypeParamsFmtOpts::Std),
+            "fn(MyStruct) -> Vec<MyStruct>"
+        );
+    }
+
+    #[test]
+    fn type_name_closure() {
+        fn type_name_of<T>(_: &T) -> String {
+            tynm::type_name::<T>()
+        }
+        fn type_namem_of<T>(_: &T, m: usize) -> String {
+            tynm::type_namem::<T>(m)
+        }
+
+        let closure = || {};
+        assert_eq!(type_name_of(&closure), "{closure in type_name_closure}");
+        assert_eq!(
+            type_namem_of(&closure, 1),
+            "{closure in tynm::..::type_name_closure}"
+        );
+        assert_eq!(
+            type_namem_of(&closure, usize::MAX),
+            "{closure in tynm::tests::type_name_closure}"
+        );
+
+        let nested = || || {};
+        assert_eq!(type_name_of(&nested()), "{closure in type_name_closure}");
+
+        let closures = Some(closure);
+        assert_eq!(
+            type_name_of(&closures),
+            "Option<{closure in type_name_closure}>"
+        );
+    }
+
+    #[test]
+    fn type_name_closure_after_generic_args() {
+        fn type_name_of<T>(_: &T) -> String {
+            tynm::type_name::<T>()
+        }
+        fn generic<T>(_: T) -> String {
+            type_name_of(&|| {})
+        }
+        trait Method {
+            fn method(&self) -> String;
+        }
+        impl<T> Method for Option<T> {
+            fn method(&self) -> String {
+                type_name_of(&|| {})
+            }
+        }
+
+        assert_eq!(generic(1u8), "{closure in generic}");
+        assert_eq!(
+            Some(1u8).method(),
+            "{closure in <Option<u8> as Method>::method}"
+        );
+    }
+
+    #[test]
+    fn write_type_name_closure_matches_type_name() {
+        fn assert_matches<T>(_: &T) {
+            [
+                TypeNameFmt::new(),
+                TypeNameFmt::new().segment_count_left(1),
+                TypeNameFmt::new().segment_count_right(1),
+                TypeNameFmt::new().segment_count_left(usize::MAX),
+                TypeNameFmt::new().type_params_fmt_opts(TypeParamsFmtOpts::None),
+            ]
+            .iter()
+            .for_each(|fmt| {
+                let mut buffer = String::new();
+                tynm::write_type_name_with::<T>(&mut buffer, fmt)
+                    .expect("Writing to a `String` never fails.");
+
+                assert_eq!(tynm::type_name_with::<T>(fmt), buffer, "`{fmt:?}`");
+            });
+        }
+        fn generic<T>(_: T) {
+            assert_matches(&|| {});
+        }
+        struct Wrapper<T>(T);
+        impl<T> Wrapper<T> {
+            fn run(&self) {
+                assert_matches(&|| {});
+            }
+        }
+        trait Method {
+            fn method(&self);
+        }
+        impl<T> Method for Option<T> {
+            fn method(&self) {
+                assert_matches(&|| {});
+            }
+        }
+
+        let closure = || {};
+        assert_matches(&closure);
+        assert_matches(&Some(closure));
+        let nested = || || {};
+        assert_matches(&nested());
+        generic(1u8);
+        Wrapper(1u8).run();
+        Some(1u8).method();
+    }
+
+    #[test]
+    fn type_name_qualified_path_opts() {
+        let type_name =
+            TypeName::from("<my_crate::MyStruct<u8> as core::iter::IntoIterator>::IntoIter");
+
+        assert_eq!(
+            type_name.as_str_opts(TypeParamsFmtOpts::All),
+            "<MyStruct<u8> as IntoIterator>::IntoIter"
+        );
+        assert_eq!(
+            type_name.as_str_opts(TypeParamsFmtOpts::Std),
+            "<MyStruct as IntoIterator>::IntoIter"
+        );
+    }
+
     #[test]
     fn type_name_display() {
         use core::sync::atomic::AtomicI8;
@@ -472,11 +1466,8 @@ mod tests {
 
     #[test]
     fn type_name_usize_mn() {
-        assert_eq!(tynm::type_namem::<usize>(usize::MAX), "::usize");
-        assert_eq!(
-            tynm::type_namemn::<usize>(usize::MAX, usize::MAX),
-            "::usize"
-        );
+        assert_eq!(tynm::type_namem::<usize>(usize::MAX), "usize");
+        assert_eq!(tynm::type_namemn::<usize>(usize::MAX, usize::MAX), "usize");
     }
 
     #[test]
@@ -484,6 +1475,151 @@ mod tests {
         assert_eq!(tynm::type_name::<dyn core::fmt::Debug>(), "dyn Debug");
     }
 
+    #[test]
+    fn type_name_trait_multiple_bounds() {
+        assert_eq!(
+            tynm::type_name::<dyn core::error::Error + Send + Sync>(),
+            "dyn Error + Send + Sync"
+        );
+        assert_eq!(
+            tynm::type_name::<&(dyn core::fmt::Debug + Send)>(),
+            "&dyn Debug + Send"
+        );
+        assert_eq!(
+            tynm::type_name::<Box<dyn core::fmt::Debug + Sync + 'static>>(),
+            "Box<dyn Debug + Sync>"
+        );
+        assert_eq!(
+            tynm::type_namem::<dyn core::error::Error + Send>(1),
+            "dyn core::..::Error + core::..::Send"
+        );
+    }
+
+    #[test]
+    fn type_name_trait_elide_auto_traits() {
+        let tn = TypeName::new::<Box<dyn core::error::Error + Send + Sync>>();
+        assert_eq!(
+            format!("{}", tn.as_display().elide_auto_traits(true)),
+            "Box<dyn Error>"
+        );
+        assert_eq!(
+            format!("{}", tn.as_display().elide_auto_traits(false)),
+            "Box<dyn Error + Send + Sync>"
+        );
+
+        let tn = TypeName::new::<dyn Send + Sync + Unpin>();
+        assert_eq!(
+            format!("{}", tn.as_display().elide_auto_traits(true)),
+            "dyn Send"
+        );
+    }
+
+    #[test]
+    fn type_name_generic_arg_binding() {
+        assert_eq!(
+            tynm::type_name::<Box<dyn Iterator<Item = String>>>(),
+            "Box<dyn Iterator<Item = String>>"
+        );
+        assert_eq!(
+            tynm::type_name::<core::pin::Pin<Box<dyn Future<Output = Result<(), String>> + Send>>>(
+            ),
+            "Pin<Box<dyn Future<Output = Result<(), String>> + Send>>"
+        );
+        assert_eq!(
+            tynm::type_namem::<Box<dyn Iterator<Item = String>>>(1),
+            "alloc::..::Box<dyn core::..::Iterator<Item = alloc::..::String>>"
+        );
+    }
+
+    #[test]
+    fn type_name_const_generics() {
+        struct ArrayVec<T, const N: usize>([T; N]);
+        struct Offset<const N: i32>;
+        struct Flag<const B: bool>;
+        struct Delimiter<const C: char>;
+
+        assert_eq!(tynm::type_name::<ArrayVec<u8, 16>>(), "ArrayVec<u8, 16>");
+        assert_eq!(tynm::type_name::<Offset<-5>>(), "Offset<-5>");
+        assert_eq!(tynm::type_name::<Flag<true>>(), "Flag<true>");
+        assert_eq!(tynm::type_name::<Delimiter<'x'>>(), "Delimiter<'x'>");
+        assert_eq!(tynm::type_name::<Delimiter<'\''>>(), "Delimiter<'\\''>");
+        assert_eq!(tynm::type_name::<Delimiter<'>'>>(), "Delimiter<'>'>");
+        assert_eq!(tynm::type_name::<Delimiter<','>>(), "Delimiter<','>");
+        assert_eq!(
+            tynm::type_name::<Vec<(Flag<false>, ArrayVec<String, 3>)>>(),
+            "Vec<(Flag<false>, ArrayVec<String, 3>)>"
+        );
+    }
+
+    #[test]
+    fn type_name_const_generics_mn_opts() {
+        struct ArrayVec<T, const N: usize>([T; N]);
+        type T = Option<ArrayVec<String, 16>>;
+
+        assert_eq!(
+            tynm::type_namem::<T>(1),
+            "core::..::Option<tynm::..::ArrayVec<alloc::..::String, 16>>"
+        );
+        assert_eq!(
+            tynm::type_namen::<T>(1),
+            "..::option::Option<..::type_name_const_generics_mn_opts::ArrayVec<..::string::String, 16>>"
+        );
+        assert_eq!(
+            tynm::type_namemn::<T>(usize::MAX, usize::MAX),
+            core::any::type_name::<T>()
+        );
+        assert_eq!(
+            tynm::type_name_opts::<T>(TypeParamsFmtOpts::Std),
+            "Option<ArrayVec>"
+        );
+    }
+
+    #[test]
+    #[allow(non_camel_case_types, non_snake_case)]
+    fn type_name_identifiers() {
+        struct my_handle;
+        struct __Private;
+        struct Über;
+        mod FFI {
+            pub struct Thing;
+            pub struct lower<T>(T);
+        }
+
+        assert_eq!(tynm::type_name::<my_handle>(), "my_handle");
+        assert_eq!(tynm::type_name::<__Private>(), "__Private");
+        assert_eq!(tynm::type_name::<Über>(), "Über");
+        assert_eq!(tynm::type_name::<FFI::Thing>(), "Thing");
+        assert_eq!(
+            tynm::type_namen::<FFI::lower<my_handle>>(1),
+            "..::FFI::lower<..::type_name_identifiers::my_handle>"
+        );
+        assert_eq!(
+            tynm::type_name::<Option<FFI::lower<Über>>>(),
+            "Option<lower<Über>>"
+        );
+    }
+
+    #[test]
+    fn type_name_trait_fn_sugar() {
+        assert_eq!(
+            tynm::type_name::<Box<dyn Fn(&str) -> Result<u32, String> + Send>>(),
+            "Box<dyn Fn(&'_ str) -> Result<u32, String> + Send>"
+        );
+        assert_eq!(tynm::type_name::<Box<dyn FnMut()>>(), "Box<dyn FnMut()>");
+        assert_eq!(
+            tynm::type_name::<Box<dyn FnOnce(u8, Vec<String>)>>(),
+            "Box<dyn FnOnce(u8, Vec<String>)>"
+        );
+        assert_eq!(
+            tynm::type_namem::<Box<dyn Fn(String) -> Option<String>>>(1),
+            "alloc::..::Box<dyn core::..::Fn(alloc::..::String) -> core::..::Option<alloc::..::String>>"
+        );
+        assert_eq!(
+            tynm::type_namen::<Box<dyn Fn(String)>>(1),
+            "..::boxed::Box<dyn ..::function::Fn(..::string::String)>"
+        );
+    }
+
     #[test]
     fn type_name_unsized_mn() {
         assert_eq!(
@@ -499,4 +1635,144 @@ mod tests {
             "dyn ..::fmt::Debug"
         );
     }
+
+    #[test]
+    fn try_type_name_returns_ok_for_parseable_types() {
+        assert_eq!(
+            tynm::try_type_name::<Option<String>>(),
+            Ok(String::from("Option<String>"))
+        );
+        assert_eq!(
+            tynm::try_type_namemn::<dyn core::fmt::Debug>(0, 1),
+            Ok(String::from("dyn ..::fmt::Debug"))
+        );
+        assert_eq!(
+            tynm::try_type_name_opts::<Vec<Option<String>>>(TypeParamsFmtOpts::Std),
+            Ok(String::from("Vec<Option<String>>"))
+        );
+    }
+
+    #[test]
+    fn type_name_with_matches_type_namemn_opts() {
+        type T = Vec<Box<dyn core::error::Error + Send + Sync>>;
+
+        [(0, 0), (1, 0), (0, 1), (1, 1)].iter().for_each(|&(m, n)| {
+            [TypeParamsFmtOpts::All, TypeParamsFmtOpts::Std]
+                .iter()
+                .for_each(|&type_params_fmt_opts| {
+                    let fmt = TypeNameFmt::new()
+                        .segment_count_left(m)
+                        .segment_count_right(n)
+                        .type_params_fmt_opts(type_params_fmt_opts);
+
+                    assert_eq!(
+                        tynm::type_namemn_opts::<T>(m, n, type_params_fmt_opts),
+                        tynm::type_name_with::<T>(&fmt)
+                    );
+                });
+        });
+
+        assert_eq!(
+            "Vec<Box<dyn Error>>",
+            tynm::type_name_with::<T>(&TypeNameFmt::new().elide_auto_traits(true))
+        );
+    }
+
+    #[test]
+    fn type_name_falls_back_to_original_string_when_not_fully_parsed() {
+        assert_eq!(
+            "my_crate::Foo<u8",
+            super::type_name_str_with("my_crate::Foo<u8", &TypeNameFmt::new())
+        );
+        assert_eq!(
+            "Foo<u8>",
+            super::type_name_str_with("my_crate::Foo<u8>", &TypeNameFmt::new())
+        );
+    }
+
+    #[test]
+    fn to_full_string_round_trips_std_type_names() {
+        use alloc::{borrow::Cow, collections::BTreeMap, rc::Rc, sync::Arc};
+        use core::{
+            any::{type_name, type_name_of_val},
+            cell::RefCell,
+            error::Error,
+            fmt::Debug,
+            marker::PhantomData,
+            ops::Range,
+            ptr::NonNull,
+        };
+
+        struct ConstUsize<const N: usize>;
+        struct ConstChar<const C: char>;
+        struct ConstBool<const B: bool>;
+        struct Lifetime<'a>(PhantomData<&'a str>);
+
+        let closure = || {};
+        let nested = || || {};
+        let async_block = async {};
+
+        fn generic<T>(_: T) -> &'static str {
+            type_name_of_val(&|| {})
+        }
+        trait Method {
+            fn method(&self) -> &'static str;
+        }
+        impl<T> Method for Option<T> {
+            fn method(&self) -> &'static str {
+                type_name_of_val(&|| {})
+            }
+        }
+
+        #[rustfmt::skip]
+        let corpus = [
+            type_name::<usize>(),
+            type_name::<str>(),
+            type_name::<[u8]>(),
+            type_name::<[u8; 3]>(),
+            type_name::<&'static str>(),
+            type_name::<&mut [u8]>(),
+            type_name::<*const u8>(),
+            type_name::<*mut Vec<u8>>(),
+            type_name::<()>(),
+            type_name::<(u8,)>(),
+            type_name::<(u8, String, ())>(),
+            type_name::<Option<String>>(),
+            type_name::<Result<Vec<u8>, Box<dyn Error + Send + Sync>>>(),
+            type_name::<BTreeMap<String, Vec<Option<u8>>>>(),
+            type_name::<Cow<'static, str>>(),
+            type_name::<Rc<RefCell<Vec<u8>>>>(),
+            type_name::<Arc<dyn Debug + Send>>(),
+            type_name::<&dyn Debug>(),
+            type_name::<dyn Iterator<Item = u8>>(),
+            type_name::<Box<dyn Fn(&str) -> u8>>(),
+            type_name::<Box<dyn for<'a> FnMut(&'a str) -> &'a str>>(),
+            type_name::<fn()>(),
+            type_name::<fn(u8, &str) -> Option<u8>>(),
+            type_name::<unsafe extern "C" fn(u8, ...)>(),
+            type_name::<for<'a> fn(&'a str) -> &'a str>(),
+            type_name::<PhantomData<fn() -> u8>>(),
+            type_name::<Range<usize>>(),
+            type_name::<NonNull<[u8]>>(),
+            type_name::<<Vec<u8> as IntoIterator>::IntoIter>(),
+            type_name::<ConstUsize<3>>(),
+            type_name::<ConstChar<'\''>>(),
+            type_name::<ConstBool<true>>(),
+            type_name::<Lifetime<'static>>(),
+            type_name_of_val(&closure),
+            type_name_of_val(&nested()),
+            type_name_of_val(&Some(closure)),
+            type_name_of_val(&async_block),
+            generic(Vec::<u8>::new()),
+            Some(1u8).method(),
+        ];
+
+        corpus.iter().for_each(|std_type_name| {
+            let type_name = TypeName::parse_strict(std_type_name)
+                .unwrap_or_else(|e| panic!("Failed to parse `{std_type_name}`: {e}"));
+
+            assert_eq!(*std_type_name, type_name.to_full_string());
+            assert_eq!(*std_type_name, format!("{}", type_name.as_display_full()));
+        });
+    }
 }
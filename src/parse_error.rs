@@ -0,0 +1,110 @@
+use core::{fmt, ops::Range};
+
+/// Error when parsing a type name string.
+///
+/// # Example
+///
+/// ```rust
+/// use tynm::{ParseErrorKind, TypeName};
+///
+/// let error = TypeName::parse("%Foo").unwrap_err();
+///
+/// assert_eq!(error.offset(), 0);
+/// assert_eq!(error.kind(), ParseErrorKind::UnexpectedChar('%'));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset in the input string where parsing failed.
+    pub(crate) offset: usize,
+    /// Kind of failure.
+    pub(crate) kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// Returns the byte offset in the input string where parsing failed.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the kind of failure.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte range in the input string that could not be parsed.
+    ///
+    /// For [`ParseErrorKind::TrailingInput`], this is the whole unconsumed
+    /// text.
+    pub fn span(&self) -> Range<usize> {
+        let len = match self.kind {
+            ParseErrorKind::UnexpectedEnd => 0,
+            ParseErrorKind::UnexpectedChar(c) => c.len_utf8(),
+            ParseErrorKind::TrailingInput { len } => len,
+        };
+
+        self.offset..self.offset + len
+    }
+
+    /// Returns a `ParseError` for the given input, where `remaining` is the
+    /// part of `input` that could not be parsed.
+    pub(crate) fn new(input: &str, remaining: &str) -> Self {
+        let offset = input.len() - remaining.len();
+        let kind = match remaining.chars().next() {
+            Some(c) => ParseErrorKind::UnexpectedChar(c),
+            None => ParseErrorKind::UnexpectedEnd,
+        };
+
+        Self { offset, kind }
+    }
+
+    /// Returns a `ParseError` for text left over after a complete type name,
+    /// where `remaining` is the unconsumed suffix of `input`.
+    pub(crate) fn trailing(input: &str, remaining: &str) -> Self {
+        let offset = input.len() - remaining.len();
+        let kind = ParseErrorKind::TrailingInput {
+            len: remaining.len(),
+        };
+
+        Self { offset, kind }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed to parse type name at byte offset {}: {}.",
+            self.offset, self.kind
+        )
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Kind of failure when parsing a type name string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input ended before the type name was complete.
+    UnexpectedEnd,
+    /// A character was encountered where it is not valid in a type name.
+    UnexpectedChar(char),
+    /// A type name was parsed, but text remains after it.
+    ///
+    /// Only returned by [`TypeName::parse_strict`].
+    ///
+    /// [`TypeName::parse_strict`]: crate::TypeName::parse_strict
+    TrailingInput {
+        /// Number of bytes that were not consumed.
+        len: usize,
+    },
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "unexpected end of input"),
+            Self::UnexpectedChar(c) => write!(f, "unexpected character `{c}`"),
+            Self::TrailingInput { len } => write!(f, "{len} bytes of unparsed trailing input"),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+/// Limits on how much of a type name string is parsed.
+///
+/// Types that are nested more deeply than `max_depth`, or that begin more than
+/// `max_len` bytes into the string, are not parsed. They are kept verbatim as
+/// [`TypeName::Elided`], which is rendered as `..`. This bounds the recursion
+/// depth when parsing and rendering deeply nested types, such as long iterator
+/// adapter chains.
+///
+/// # Example
+///
+/// ```rust
+/// use tynm::{ParseLimits, TypeName};
+///
+/// let limits = ParseLimits::new().max_depth(2);
+/// let type_name = TypeName::parse_with_limits(
+///     "core::option::Option<alloc::vec::Vec<alloc::vec::Vec<u8>>>",
+///     &limits,
+/// )
+/// .unwrap();
+///
+/// assert_eq!("Option<Vec<..>>", type_name.as_str());
+/// ```
+///
+/// [`TypeName::Elided`]: crate::TypeName::Elided
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Maximum number of nested type levels to parse.
+    pub(crate) max_depth: usize,
+    /// Maximum byte offset in the input at which a type is parsed.
+    pub(crate) max_len: usize,
+}
+
+impl ParseLimits {
+    /// Default maximum number of nested type levels to parse.
+    ///
+    /// This is deep enough for type names seen in practice, while keeping the
+    /// stack used by parsing well within the default thread stack size.
+    pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+    /// Returns `ParseLimits` with a maximum depth of [`DEFAULT_MAX_DEPTH`],
+    /// and no maximum length.
+    ///
+    /// [`DEFAULT_MAX_DEPTH`]: Self::DEFAULT_MAX_DEPTH
+    pub const fn new() -> Self {
+        Self {
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+            max_len: usize::MAX,
+        }
+    }
+
+    /// Sets the maximum number of nested type levels to parse.
+    ///
+    /// The outermost type is at depth 0, so `max_depth(1)` parses `Vec<u8>` as
+    /// `Vec<..>`.
+    pub const fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the maximum byte offset in the input at which a type is parsed.
+    ///
+    /// Types that begin at or after this offset are elided, e.g. with a
+    /// `max_len` of 20, `alloc::vec::Vec<u8>` is parsed in full, and
+    /// `(alloc::string::String, u8)` as `(String, ..)`.
+    pub const fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,290 +1,1498 @@
-use alloc::{boxed::Box, vec, vec::Vec};
-
-use nom::{
-    branch::alt,
-    bytes::complete::{tag, take_until, take_while},
-    character::complete::char,
-    combinator::opt,
-    multi::separated_list0,
-    sequence::{delimited, pair},
-    IResult, Parser,
-};
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
 
-use crate::types::{
-    TypeName, TypeNameArray, TypeNameReference, TypeNameSlice, TypeNameStruct, TypeNameTrait,
-    TypeNameTuple,
+use crate::{
+    closure_kind::{closure_kind, ClosureKind},
+    types::{
+        GenericArg, TypeName, TypeNameArray, TypeNameBinding, TypeNameClosure, TypeNameFunction,
+        TypeNamePointer, TypeNameQualifiedPath, TypeNameReference, TypeNameSlice, TypeNameStruct,
+        TypeNameTrait, TypeNameTraitBound, TypeNameTuple,
+    },
+    ParseLimits,
 };
 
-/// List of known primitive types
-///
-/// Note: Arrays and slices are not included in this list as their type name depends on the type
-/// parameter (and length).
-#[rustfmt::skip]
-const PRIMITIVE_TYPES: &[&str] = &[
-    // From side bar on <https://doc.rust-lang.org/std/primitive.slice.html>
-    // "array", [T; n]
-    "bool",
-    "char",
-    "f32",
-    "f64",
-    // "fn", // TODO
-    "i128",
-    "i16",
-    "i32",
-    "i64",
-    "i8",
-    "isize",
-    // "never", !
-    // "pointer", *
-    // "reference", &
-    // "slice", [T]
-    "str",
-    // "tuple", (T, ..)
-    "u128",
-    "u16",
-    "u32",
-    "u64",
-    "u8",
-    // "unit", ()
-    "usize",
-];
-
-pub fn is_alphanumeric_underscore(c: char) -> bool {
-    c == '_' || c.is_ascii_alphanumeric()
-}
-
-pub fn is_lowercase_alphanumeric_underscore(c: char) -> bool {
-    c == '_' || c.is_ascii_digit() || c.is_ascii_lowercase()
-}
-
-pub fn module_name(input: &str) -> IResult<&str, &str> {
-    if let Some(first_char) = input.chars().next() {
-        if first_char.is_ascii_alphabetic() && first_char.is_ascii_lowercase() {
-            take_while(is_lowercase_alphanumeric_underscore).parse(input)
-        } else {
-            Ok((input, ""))
+/// Result of a parser: the remaining input and the parsed value, or the input
+/// at which parsing failed.
+pub type ParseResult<'s, O> = Result<(&'s str, O), &'s str>;
+
+/// Nesting depth and position of the type being parsed, used to stop parsing
+/// at the [`ParseLimits`].
+#[derive(Clone, Copy, Debug)]
+pub struct Nesting {
+    /// Number of types that enclose the type being parsed.
+    depth: usize,
+    /// Length of the whole input, used to compute the current position.
+    input_len: usize,
+    /// Limits to stop parsing at.
+    limits: ParseLimits,
+}
+
+impl Nesting {
+    /// Returns the `Nesting` for the outermost type of the given input.
+    pub fn new(input: &str, limits: ParseLimits) -> Self {
+        Self {
+            depth: 0,
+            input_len: input.len(),
+            limits,
         }
-    } else {
-        Ok((input, ""))
+    }
+
+    /// Returns the `Nesting` for a type enclosed by the current one.
+    fn nested(self) -> Self {
+        Self {
+            depth: self.depth + 1,
+            ..self
+        }
+    }
+
+    /// Returns whether a type beginning at `input` exceeds the limits.
+    fn is_exceeded(self, input: &str) -> bool {
+        let offset = self.input_len - input.len();
+        self.depth >= self.limits.max_depth || offset >= self.limits.max_len
     }
 }
 
-pub fn module_path(input: &str) -> IResult<&str, Vec<&str>> {
-    separated_list0(tag("::"), module_name)
-        .parse(input)
-        .map(|(input, mut module_names)| {
-            module_names.retain(|module_name| !module_name.is_empty());
-            (input, module_names)
+/// Builds the values that the parser recognizes, so that the same parser can
+/// produce representations other than the [`TypeName`] tree.
+///
+/// Lists are built by pushing each element once it is parsed. A list is passed
+/// to the builder before any list that was started earlier is pushed to
+/// again, so the builder may keep the elements of unfinished lists on a stack.
+///
+/// Text is always a slice of the parsed input.
+pub trait Builder<'s> {
+    /// A type, e.g. `u8` or `&str`.
+    type Type: Clone;
+    /// A generic argument, e.g. `u8`, `16`, or `Item = u8`.
+    type GenericArg;
+    /// A path with generic arguments, e.g. `alloc::vec::Vec<u8>`.
+    type Struct: Clone;
+    /// A bound of a trait object, e.g. `core::fmt::Debug`.
+    type TraitBound;
+    /// A list of types that is being parsed.
+    type Types;
+    /// A list of generic arguments that is being parsed.
+    type GenericArgs;
+    /// A list of trait bounds that is being parsed.
+    type TraitBounds;
+    /// A list of lifetimes that is being parsed.
+    type Lifetimes;
+    /// What has been built at some point, which the builder can be rolled back
+    /// to.
+    type Checkpoint: Copy;
+
+    /// Returns a checkpoint of what has been built so far.
+    fn checkpoint(&self) -> Self::Checkpoint;
+
+    /// Discards what was built after the checkpoint, which is done when a
+    /// parser fails and another alternative is tried.
+    fn rollback(&mut self, checkpoint: Self::Checkpoint);
+
+    /// Starts a list of types.
+    fn types(&mut self) -> Self::Types;
+
+    /// Adds a type to a list.
+    fn push_type(&mut self, types: &mut Self::Types, type_name: Self::Type);
+
+    /// Starts a list of generic arguments.
+    fn generic_args(&mut self) -> Self::GenericArgs;
+
+    /// Adds a generic argument to a list.
+    fn push_generic_arg(
+        &mut self,
+        generic_args: &mut Self::GenericArgs,
+        generic_arg: Self::GenericArg,
+    );
+
+    /// Starts a list of trait bounds.
+    fn trait_bounds(&mut self) -> Self::TraitBounds;
+
+    /// Adds a trait bound to a list.
+    fn push_trait_bound(&mut self, bounds: &mut Self::TraitBounds, bound: Self::TraitBound);
+
+    /// Starts a list of lifetimes.
+    fn lifetimes(&mut self) -> Self::Lifetimes;
+
+    /// Adds a lifetime to a list.
+    fn push_lifetime(&mut self, lifetimes: &mut Self::Lifetimes, lifetime: &'s str);
+
+    /// Returns an empty type.
+    fn none(&mut self) -> Self::Type;
+
+    /// Returns the never type, `!`.
+    fn never(&mut self) -> Self::Type;
+
+    /// Returns the unit type, `()`.
+    fn unit(&mut self) -> Self::Type;
+
+    /// Returns a type that could not be parsed.
+    fn unknown(&mut self, text: &'s str) -> Self::Type;
+
+    /// Returns a type that exceeded the parse limits.
+    fn elided(&mut self, text: &'s str) -> Self::Type;
+
+    /// Returns an array type, e.g. `[u8; 4]`.
+    fn array(&mut self, type_param: Self::Type, len: &'s str) -> Self::Type;
+
+    /// Returns a slice type, e.g. `[u8]`.
+    fn slice(&mut self, type_param: Self::Type) -> Self::Type;
+
+    /// Returns a reference type, e.g. `&'a mut u8`.
+    fn reference(
+        &mut self,
+        lifetime: Option<&'s str>,
+        mutable: bool,
+        type_param: Self::Type,
+    ) -> Self::Type;
+
+    /// Returns a pointer type, e.g. `*const u8`.
+    fn pointer(&mut self, const_or_mut: &'s str, type_param: Self::Type) -> Self::Type;
+
+    /// Returns a function pointer type, e.g. `unsafe fn(u8) -> u8`.
+    fn function(
+        &mut self,
+        bound_lifetimes: Self::Lifetimes,
+        is_unsafe: bool,
+        abi: Option<&'s str>,
+        params: Self::Types,
+        variadic: bool,
+        return_type: Option<Self::Type>,
+    ) -> Self::Type;
+
+    /// Returns a qualified path type, e.g. `<T as Trait>::Assoc`.
+    ///
+    /// `assoc_path` is the `::` separated path after the `>`, which may be
+    /// empty when the path continues with a method's generic arguments.
+    fn qualified_path(
+        &mut self,
+        self_type: Self::Type,
+        trait_path: Option<Self::Struct>,
+        assoc_path: &'s str,
+    ) -> Self::Type;
+
+    /// Returns a path with generic arguments.
+    ///
+    /// `module_path` is the `::` separated path before the simple name, which
+    /// may be empty.
+    fn struct_path(
+        &mut self,
+        module_path: &'s str,
+        simple_name: &'s str,
+        type_params: Option<Self::GenericArgs>,
+    ) -> Self::Struct;
+
+    /// Returns the type named by a path with generic arguments.
+    fn struct_type(&mut self, type_name_struct: Self::Struct) -> Self::Type;
+
+    /// Returns a closure or coroutine type.
+    ///
+    /// `module_path` and `enclosing_path` are `::` separated paths, which may
+    /// be empty.
+    fn closure(
+        &mut self,
+        kind: ClosureKind,
+        parent_path: Self::Types,
+        module_path: &'s str,
+        segment: &'s str,
+        enclosing_path: &'s str,
+    ) -> Self::Type;
+
+    /// Returns a tuple type.
+    fn tuple(&mut self, type_params: Self::Types) -> Self::Type;
+
+    /// Returns a trait object type.
+    fn trait_object(
+        &mut self,
+        bounds: Self::TraitBounds,
+        lifetime_bounds: Self::Lifetimes,
+        parenthesized: bool,
+    ) -> Self::Type;
+
+    /// Returns a bound of a trait object.
+    fn trait_bound(
+        &mut self,
+        bound_lifetimes: Self::Lifetimes,
+        inner: Self::Struct,
+        fn_params: Option<Self::Types>,
+        fn_return_type: Option<Self::Type>,
+    ) -> Self::TraitBound;
+
+    /// Returns a type generic argument.
+    fn type_arg(&mut self, type_name: Self::Type) -> Self::GenericArg;
+
+    /// Returns a const generic argument.
+    fn const_arg(&mut self, value: &'s str) -> Self::GenericArg;
+
+    /// Returns a lifetime generic argument.
+    fn lifetime_arg(&mut self, lifetime: &'s str) -> Self::GenericArg;
+
+    /// Returns an associated type binding generic argument.
+    fn binding_arg(&mut self, name: &'s str, type_name: Self::Type) -> Self::GenericArg;
+}
+
+/// Builds [`TypeName`] trees.
+pub struct TreeBuilder;
+
+impl<'s> Builder<'s> for TreeBuilder {
+    // Values of failed alternatives are dropped by the parser, so there is
+    // nothing to roll back.
+    type Checkpoint = ();
+    type GenericArg = GenericArg<'s>;
+    type GenericArgs = Vec<GenericArg<'s>>;
+    type Lifetimes = Vec<Cow<'s, str>>;
+    type Struct = TypeNameStruct<'s>;
+    type TraitBound = TypeNameTraitBound<'s>;
+    type TraitBounds = Vec<TypeNameTraitBound<'s>>;
+    type Type = TypeName<'s>;
+    type Types = Vec<TypeName<'s>>;
+
+    fn checkpoint(&self) -> Self::Checkpoint {}
+
+    fn rollback(&mut self, _checkpoint: Self::Checkpoint) {}
+
+    fn types(&mut self) -> Self::Types {
+        Vec::new()
+    }
+
+    fn push_type(&mut self, types: &mut Self::Types, type_name: Self::Type) {
+        types.push(type_name);
+    }
+
+    fn generic_args(&mut self) -> Self::GenericArgs {
+        Vec::new()
+    }
+
+    fn push_generic_arg(
+        &mut self,
+        generic_args: &mut Self::GenericArgs,
+        generic_arg: Self::GenericArg,
+    ) {
+        generic_args.push(generic_arg);
+    }
+
+    fn trait_bounds(&mut self) -> Self::TraitBounds {
+        Vec::new()
+    }
+
+    fn push_trait_bound(&mut self, bounds: &mut Self::TraitBounds, bound: Self::TraitBound) {
+        bounds.push(bound);
+    }
+
+    fn lifetimes(&mut self) -> Self::Lifetimes {
+        Vec::new()
+    }
+
+    fn push_lifetime(&mut self, lifetimes: &mut Self::Lifetimes, lifetime: &'s str) {
+        lifetimes.push(Cow::Borrowed(lifetime));
+    }
+
+    fn none(&mut self) -> Self::Type {
+        TypeName::None
+    }
+
+    fn never(&mut self) -> Self::Type {
+        TypeName::Never
+    }
+
+    fn unit(&mut self) -> Self::Type {
+        TypeName::Unit
+    }
+
+    fn unknown(&mut self, text: &'s str) -> Self::Type {
+        TypeName::Unknown(Cow::Borrowed(text))
+    }
+
+    fn elided(&mut self, text: &'s str) -> Self::Type {
+        TypeName::Elided(Cow::Borrowed(text))
+    }
+
+    fn array(&mut self, type_param: Self::Type, len: &'s str) -> Self::Type {
+        TypeName::Array(TypeNameArray {
+            type_param: Box::new(type_param),
+            len: Cow::Borrowed(len),
+        })
+    }
+
+    fn slice(&mut self, type_param: Self::Type) -> Self::Type {
+        TypeName::Slice(TypeNameSlice {
+            type_param: Box::new(type_param),
+        })
+    }
+
+    fn reference(
+        &mut self,
+        lifetime: Option<&'s str>,
+        mutable: bool,
+        type_param: Self::Type,
+    ) -> Self::Type {
+        TypeName::Reference(TypeNameReference {
+            lifetime: lifetime.map(Cow::Borrowed),
+            mutable,
+            type_param: Box::new(type_param),
         })
+    }
+
+    fn pointer(&mut self, const_or_mut: &'s str, type_param: Self::Type) -> Self::Type {
+        TypeName::Pointer(TypeNamePointer {
+            const_or_mut: Cow::Borrowed(const_or_mut),
+            type_param: Box::new(type_param),
+        })
+    }
+
+    fn function(
+        &mut self,
+        bound_lifetimes: Self::Lifetimes,
+        is_unsafe: bool,
+        abi: Option<&'s str>,
+        params: Self::Types,
+        variadic: bool,
+        return_type: Option<Self::Type>,
+    ) -> Self::Type {
+        TypeName::Function(TypeNameFunction {
+            bound_lifetimes,
+            is_unsafe,
+            abi: abi.map(Cow::Borrowed),
+            params,
+            variadic,
+            return_type: return_type.map(Box::new),
+        })
+    }
+
+    fn qualified_path(
+        &mut self,
+        self_type: Self::Type,
+        trait_path: Option<Self::Struct>,
+        assoc_path: &'s str,
+    ) -> Self::Type {
+        TypeName::QualifiedPath(TypeNameQualifiedPath {
+            self_type: Box::new(self_type),
+            trait_path,
+            assoc_path: borrowed_segments(assoc_path),
+        })
+    }
+
+    fn struct_path(
+        &mut self,
+        module_path: &'s str,
+        simple_name: &'s str,
+        type_params: Option<Self::GenericArgs>,
+    ) -> Self::Struct {
+        TypeNameStruct {
+            module_path: borrowed_segments(module_path),
+            simple_name: Cow::Borrowed(simple_name),
+            type_params: type_params.unwrap_or_default(),
+        }
+    }
+
+    fn struct_type(&mut self, type_name_struct: Self::Struct) -> Self::Type {
+        TypeName::Struct(type_name_struct)
+    }
+
+    fn closure(
+        &mut self,
+        kind: ClosureKind,
+        parent_path: Self::Types,
+        module_path: &'s str,
+        segment: &'s str,
+        enclosing_path: &'s str,
+    ) -> Self::Type {
+        TypeName::Closure(TypeNameClosure {
+            kind,
+            parent_path,
+            module_path: borrowed_segments(module_path),
+            segment: Cow::Borrowed(segment),
+            enclosing_path: borrowed_segments(enclosing_path),
+        })
+    }
+
+    fn tuple(&mut self, type_params: Self::Types) -> Self::Type {
+        TypeName::Tuple(TypeNameTuple { type_params })
+    }
+
+    fn trait_object(
+        &mut self,
+        bounds: Self::TraitBounds,
+        lifetime_bounds: Self::Lifetimes,
+        parenthesized: bool,
+    ) -> Self::Type {
+        TypeName::Trait(TypeNameTrait {
+            bounds,
+            lifetime_bounds,
+            parenthesized,
+        })
+    }
+
+    fn trait_bound(
+        &mut self,
+        bound_lifetimes: Self::Lifetimes,
+        inner: Self::Struct,
+        fn_params: Option<Self::Types>,
+        fn_return_type: Option<Self::Type>,
+    ) -> Self::TraitBound {
+        TypeNameTraitBound {
+            bound_lifetimes,
+            inner,
+            fn_params,
+            fn_return_type: fn_return_type.map(Box::new),
+        }
+    }
+
+    fn type_arg(&mut self, type_name: Self::Type) -> Self::GenericArg {
+        GenericArg::Type(type_name)
+    }
+
+    fn const_arg(&mut self, value: &'s str) -> Self::GenericArg {
+        GenericArg::Const(Cow::Borrowed(value))
+    }
+
+    fn lifetime_arg(&mut self, lifetime: &'s str) -> Self::GenericArg {
+        GenericArg::Lifetime(Cow::Borrowed(lifetime))
+    }
+
+    fn binding_arg(&mut self, name: &'s str, type_name: Self::Type) -> Self::GenericArg {
+        GenericArg::Binding(TypeNameBinding {
+            name: Cow::Borrowed(name),
+            type_name,
+        })
+    }
 }
 
-pub fn type_simple_name(input: &str) -> IResult<&str, &str> {
-    if let Some(first_char) = input.chars().next() {
-        if first_char.is_ascii_alphabetic() && first_char.is_ascii_uppercase() {
-            take_while(is_alphanumeric_underscore).parse(input)
-        } else {
-            Ok((input, ""))
+/// Returns the segments of a `::` separated path as borrowed `Cow`s.
+fn borrowed_segments(path: &str) -> Vec<Cow<'_, str>> {
+    path_segments(path).map(Cow::Borrowed).collect()
+}
+
+/// Runs the parser, discarding what it built if it fails.
+fn attempt<'s, B, O>(
+    builder: &mut B,
+    parser: impl FnOnce(&mut B) -> ParseResult<'s, O>,
+) -> ParseResult<'s, O>
+where
+    B: Builder<'s>,
+{
+    let checkpoint = builder.checkpoint();
+    let result = parser(builder);
+    if result.is_err() {
+        builder.rollback(checkpoint);
+    }
+
+    result
+}
+
+/// Parses the given text from the start of the input.
+fn tag<'s>(input: &'s str, tag: &str) -> ParseResult<'s, &'s str> {
+    match input.strip_prefix(tag) {
+        Some(remaining) => Ok((remaining, &input[..tag.len()])),
+        None => Err(input),
+    }
+}
+
+/// Returns the part of `input` before `remaining`, which must be a suffix of
+/// `input`.
+fn consumed<'s>(input: &'s str, remaining: &'s str) -> &'s str {
+    &input[..input.len() - remaining.len()]
+}
+
+/// Parses the longest prefix whose characters match the predicate, which may
+/// be empty.
+fn take_while(input: &str, predicate: impl Fn(char) -> bool) -> (&str, &str) {
+    let end = input
+        .char_indices()
+        .find(|(_, c)| !predicate(*c))
+        .map_or(input.len(), |(index, _)| index);
+
+    (&input[end..], &input[..end])
+}
+
+/// Parses the longest prefix whose characters match the predicate, which must
+/// not be empty.
+fn take_while1(input: &str, predicate: impl Fn(char) -> bool) -> ParseResult<'_, &str> {
+    match take_while(input, predicate) {
+        (_, "") => Err(input),
+        (remaining, taken) => Ok((remaining, taken)),
+    }
+}
+
+/// Parses the text up to but excluding the given text, which must be present.
+fn take_until<'s>(input: &'s str, until: &str) -> ParseResult<'s, &'s str> {
+    match input.find(until) {
+        Some(index) => Ok((&input[index..], &input[..index])),
+        None => Err(input),
+    }
+}
+
+/// Parses one or more elements separated by `separator`, where `element` adds
+/// each element to the list that is being built.
+///
+/// A separator that is not followed by an element is not consumed.
+fn separated_list1<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    separator: &str,
+    mut element: impl FnMut(&mut B, &'s str) -> ParseResult<'s, ()>,
+) -> ParseResult<'s, ()>
+where
+    B: Builder<'s>,
+{
+    let (mut input, ()) = element(builder, input)?;
+
+    while let Some(remaining) = input.strip_prefix(separator) {
+        match attempt(builder, |builder| element(builder, remaining)) {
+            Ok((remaining, ())) => input = remaining,
+            Err(_) => break,
         }
-    } else {
-        Ok((input, ""))
     }
+
+    Ok((input, ()))
 }
 
-pub fn type_parameters(input: &str) -> IResult<&str, Vec<TypeName<'_>>> {
-    opt(delimited(
-        char('<'),
-        separated_list0(tag(", "), type_name),
-        char('>'),
-    ))
-    .parse(input)
-    .map(|(input, type_params)| (input, type_params.unwrap_or_default()))
-}
-
-pub fn array_length(input: &str) -> IResult<&str, Option<&str>> {
-    pair(tag("; "), take_until("]"))
-        .parse(input)
-        .map(|(input, (_, len))| (input, Some(len)))
-        .or_else(|e| {
-            if let nom::Err::Error(nom::error::Error { input, code: _ }) = e {
-                Ok((input, None))
-            } else {
-                Err(e)
-            }
-        })
+/// Parses zero or more elements separated by `separator`, where `element` adds
+/// each element to the list that is being built.
+///
+/// A separator that is not followed by an element is not consumed.
+fn separated_list0<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    separator: &str,
+    element: impl FnMut(&mut B, &'s str) -> ParseResult<'s, ()>,
+) -> &'s str
+where
+    B: Builder<'s>,
+{
+    attempt(builder, |builder| {
+        separated_list1(builder, input, separator, element)
+    })
+    .map_or(input, |(remaining, ())| remaining)
 }
 
-pub fn array_or_slice_internal(input: &str) -> IResult<&str, TypeName<'_>> {
-    (type_name, array_length)
-        .parse(input)
-        .map(|(input, (type_param, len))| {
-            let type_param = Box::new(type_param);
-            if let Some(len) = len {
-                (input, TypeName::Array(TypeNameArray { type_param, len }))
-            } else {
-                (input, TypeName::Slice(TypeNameSlice { type_param }))
-            }
-        })
+/// Returns whether the character may be part of an identifier.
+///
+/// This includes non-ASCII alphanumeric characters, which Rust allows in
+/// identifiers.
+pub fn is_identifier_char(c: char) -> bool {
+    c == '_' || c.is_alphanumeric()
 }
 
-pub fn array_or_slice(input: &str) -> IResult<&str, TypeName<'_>> {
-    delimited(char('['), array_or_slice_internal, char(']')).parse(input)
+/// Parses an identifier, e.g. `Vec`, `my_handle`, `r#type`, or `Über`.
+pub fn identifier(input: &str) -> ParseResult<'_, &str> {
+    let unprefixed = input.strip_prefix("r#").unwrap_or(input);
+    let (remaining, ident) = take_while1(unprefixed, is_identifier_char)?;
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(unprefixed);
+    }
+
+    Ok((remaining, consumed(input, remaining)))
 }
 
-pub fn parse_reference(input: &str) -> IResult<&str, TypeName<'_>> {
-    (char('&'), opt(tag("mut")), opt(char(' ')), type_name)
-        .parse(input)
-        .map(|(input, (_, mut_str, _, type_param))| {
-            let type_param = Box::new(type_param);
-            (
-                input,
-                TypeName::Reference(TypeNameReference {
-                    mutable: mut_str.is_some(),
-                    type_param,
-                }),
-            )
-        })
+/// Parses a `{..}` path segment, such as `{{closure}}` or `{closure#0}`,
+/// including nested braces.
+pub fn brace_segment(input: &str) -> ParseResult<'_, &str> {
+    if !input.starts_with('{') {
+        return Err(input);
+    }
+
+    let mut depth = 0usize;
+    let end = input.char_indices().find_map(|(index, c)| {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => {}
+        }
+        (depth == 0).then_some(index + c.len_utf8())
+    });
+
+    match end {
+        Some(end) => Ok((&input[end..], &input[..end])),
+        None => Err(input),
+    }
+}
+
+/// Parses a path segment, which is either an identifier or a `{..}` segment.
+pub fn path_segment(input: &str) -> ParseResult<'_, &str> {
+    identifier(input).or_else(|_| brace_segment(input))
 }
 
-pub fn parse_unit(input: &str) -> IResult<&str, TypeName<'_>> {
-    tag("()")
-        .parse(input)
-        .map(|(input, _)| (input, TypeName::Unit))
+/// Parses a path, e.g. `alloc::vec::Vec`, returning its text.
+///
+/// Segments are not distinguished by their case, so the last segment is the
+/// type name and the preceding segments are its module path.
+pub fn path(input: &str) -> ParseResult<'_, &str> {
+    let (mut remaining, _) = path_segment(input)?;
+    while let Some(Ok((rest, _))) = remaining.strip_prefix("::").map(path_segment) {
+        remaining = rest;
+    }
+
+    Ok((remaining, consumed(input, remaining)))
 }
 
-pub fn parse_tuple(input: &str) -> IResult<&str, TypeName<'_>> {
-    delimited(
-        char('('),
-        separated_list0(tag(", "), type_name),
-        (opt(char(',')), char(')')),
-    )
-    .parse(input)
-    .map(|(input, type_params)| {
-        let type_name_tuple = TypeName::Tuple(TypeNameTuple { type_params });
-        (input, type_name_tuple)
+/// Returns the segments of a `::` separated path.
+///
+/// Text that is not a path segment is returned as one segment, and an empty
+/// path has no segments.
+pub fn path_segments(path: &str) -> impl Iterator<Item = &str> + Clone {
+    let mut remaining = path;
+    core::iter::from_fn(move || {
+        if remaining.is_empty() {
+            return None;
+        }
+
+        let (rest, segment) = path_segment(remaining).unwrap_or(("", remaining));
+        remaining = rest.strip_prefix("::").unwrap_or(rest);
+        Some(segment)
     })
 }
 
-pub fn parse_unit_or_tuple(input: &str) -> IResult<&str, TypeName<'_>> {
-    alt((parse_unit, parse_tuple)).parse(input)
+/// Splits a path that [`path`] parsed into its module path and its last
+/// segment.
+fn split_last_segment(path: &str) -> (&str, &str) {
+    let mut module_path_end = 0;
+    let mut last_start = 0;
+    let mut remaining = path;
+    while let Ok((rest, _)) = path_segment(remaining) {
+        match rest.strip_prefix("::") {
+            Some(next) if !next.is_empty() => {
+                module_path_end = path.len() - rest.len();
+                last_start = path.len() - next.len();
+                remaining = next;
+            }
+            _ => break,
+        }
+    }
+
+    (&path[..module_path_end], &path[last_start..])
+}
+
+/// Parses an associated type binding, e.g. `Item = u8`.
+pub fn binding<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::GenericArg>
+where
+    B: Builder<'s>,
+{
+    let (input, name) = identifier(input)?;
+    let (input, _) = tag(input, " = ")?;
+    let (input, type_name) = type_name(builder, input, nesting)?;
+
+    Ok((input, builder.binding_arg(name, type_name)))
+}
+
+/// Parses a numeric literal, e.g. `16` or `-1`.
+pub fn const_number(input: &str) -> ParseResult<'_, &str> {
+    let unsigned = input.strip_prefix('-').unwrap_or(input);
+    let (remaining, _) = take_while1(unsigned, |c: char| c.is_ascii_digit())?;
+    let (remaining, _) = take_while(remaining, is_identifier_char);
+
+    Ok((remaining, consumed(input, remaining)))
 }
 
-pub fn named_primitive(input: &str) -> Option<IResult<&str, TypeName<'_>>> {
-    if let Some(prim_type) = PRIMITIVE_TYPES
-        .iter()
-        .find(|prim_type| input.starts_with(*prim_type))
-    {
-        let remainder = &input[prim_type.len()..];
-        let next_char = remainder.chars().next();
-        let mut is_primitive_type = false;
-        if next_char.is_none() {
-            is_primitive_type = true;
-        } else if let Some(c) = next_char {
-            is_primitive_type = !is_lowercase_alphanumeric_underscore(c);
+/// Parses a `bool` literal, i.e. `true` or `false`.
+pub fn const_bool(input: &str) -> ParseResult<'_, &str> {
+    let (remaining, value) = tag(input, "true").or_else(|_| tag(input, "false"))?;
+    if remaining.starts_with(is_identifier_char) {
+        return Err(remaining);
+    }
+
+    Ok((remaining, value))
+}
+
+/// Parses a `char` literal, e.g. `'x'`, `'\''`, or `'\u{1f980}'`.
+pub fn const_char(input: &str) -> ParseResult<'_, &str> {
+    let (remaining, _) = tag(input, "'")?;
+    let remaining = match remaining.strip_prefix('\\') {
+        Some(escaped) => {
+            let mut chars = escaped.chars();
+            chars.next().ok_or(escaped)?;
+            take_until(chars.as_str(), "'")?.0
         }
+        None => match remaining.chars().next() {
+            Some(c) if c != '\'' && c != '\\' => &remaining[c.len_utf8()..],
+            _ => return Err(remaining),
+        },
+    };
+    let (remaining, _) = tag(remaining, "'")?;
 
-        if is_primitive_type {
-            // Treat this as a type.
-            Some(Ok((
-                remainder,
-                TypeName::Struct(TypeNameStruct {
-                    module_path: vec![],
-                    simple_name: prim_type,
-                    type_params: vec![],
-                }),
-            )))
-        } else {
-            None
+    Ok((remaining, consumed(input, remaining)))
+}
+
+/// Parses a const block, e.g. `{ N + 1 }`.
+///
+/// Closure segments such as `{async fn body of my_crate::run()}` are not
+/// treated as const blocks.
+pub fn const_block(input: &str) -> ParseResult<'_, &str> {
+    match brace_segment(input)? {
+        (_, segment) if closure_kind(segment).is_some() => Err(input),
+        parsed => Ok(parsed),
+    }
+}
+
+/// Parses a const generic argument, e.g. `16`, `-1`, `true`, `'x'`, or `{ N +
+/// 1 }`.
+pub fn const_arg(input: &str) -> ParseResult<'_, &str> {
+    const_number(input)
+        .or_else(|_| const_bool(input))
+        .or_else(|_| const_char(input))
+        .or_else(|_| const_block(input))
+}
+
+/// Parses a generic argument, e.g. `'_`, `u8`, `16`, or `Item = u8`.
+pub fn generic_arg<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::GenericArg>
+where
+    B: Builder<'s>,
+{
+    // Char literals are parsed before lifetimes, as `'x'` begins with the
+    // lifetime `'x`.
+    if let Ok((input, value)) = const_arg(input) {
+        return Ok((input, builder.const_arg(value)));
+    }
+    if let Ok((input, lifetime)) = lifetime(input) {
+        return Ok((input, builder.lifetime_arg(lifetime)));
+    }
+    if let Ok(parsed) = attempt(builder, |builder| binding(builder, input, nesting)) {
+        return Ok(parsed);
+    }
+
+    let (input, type_name) = type_name(builder, input, nesting)?;
+    Ok((input, builder.type_arg(type_name)))
+}
+
+/// Returns whether the character ends an element of a delimited list, such as a
+/// generic argument, tuple field, or function parameter.
+pub fn is_list_delimiter(c: char) -> bool {
+    matches!(c, ',' | '>' | ')' | ']' | ';')
+}
+
+/// Captures verbatim text up to the next list delimiter that is not nested
+/// within `<>`, `()`, `[]`, or `{}`.
+pub fn unknown(input: &str) -> ParseResult<'_, &str> {
+    let mut depth = 0usize;
+    let mut remaining = input;
+    while let Some(c) = remaining.chars().next() {
+        if let Some(rest) = remaining.strip_prefix("->") {
+            remaining = rest;
+            continue;
         }
-    } else {
-        None
-    }
-}
-
-pub fn struct_type(input: &str) -> IResult<&str, TypeNameStruct<'_>> {
-    // Parse this as a module name
-    (module_path, type_simple_name, type_parameters)
-        .parse(input)
-        .map(|(s, (module_path, simple_name, type_params))| {
-            (
-                s,
-                TypeNameStruct {
-                    module_path,
-                    simple_name,
-                    type_params,
-                },
-            )
-        })
+        // Skip char literals so that `'>'` is not treated as a delimiter.
+        if let Ok((rest, _)) = const_char(remaining) {
+            remaining = rest;
+            continue;
+        }
+
+        match c {
+            '<' | '(' | '[' | '{' => depth += 1,
+            '>' | ')' | ']' | '}' if depth > 0 => depth -= 1,
+            _ if depth == 0 && is_list_delimiter(c) => break,
+            _ => {}
+        }
+        remaining = &remaining[c.len_utf8()..];
+    }
+
+    match consumed(input, remaining) {
+        "" => Err(input),
+        text => Ok((remaining, text)),
+    }
 }
 
-pub fn named_primitive_or_struct(input: &str) -> IResult<&str, TypeName<'_>> {
-    // Check for primitive types first, otherwise we cannot distinguish the
-    // `std::u32` module from `u32` (type).
-    //
-    // We shouldn't have to worry about `use crate::u32; u32::SomeType` as the type
-    // name input should be the fully qualified crate name as determined by
-    // Rust.
-    if let Some(result) = named_primitive(input) {
-        result
+/// Parses an element of a delimited list, falling back to an unknown type
+/// when `parser` fails or does not end at a list delimiter.
+pub fn list_element<'s, B, O>(
+    builder: &mut B,
+    input: &'s str,
+    parser: impl FnOnce(&mut B, &'s str) -> ParseResult<'s, O>,
+    unknown_fn: impl FnOnce(&mut B, B::Type) -> O,
+) -> ParseResult<'s, O>
+where
+    B: Builder<'s>,
+{
+    let checkpoint = builder.checkpoint();
+    match parser(builder, input) {
+        Ok((remaining, value))
+            if remaining.is_empty() || remaining.starts_with(is_list_delimiter) =>
+        {
+            Ok((remaining, value))
+        }
+        _ => {
+            builder.rollback(checkpoint);
+
+            let (remaining, text) = unknown(input)?;
+            let type_name = builder.unknown(text);
+            Ok((remaining, unknown_fn(builder, type_name)))
+        }
+    }
+}
+
+/// Parses `<..>` generic arguments, if present.
+pub fn type_parameters<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> (&'s str, Option<B::GenericArgs>)
+where
+    B: Builder<'s>,
+{
+    let Ok((remaining, _)) = tag(input, "<") else {
+        return (input, None);
+    };
+
+    let checkpoint = builder.checkpoint();
+    let mut type_params = builder.generic_args();
+    let remaining = separated_list0(builder, remaining, ", ", |builder, input| {
+        let (input, generic_arg) = list_element(
+            builder,
+            input,
+            |builder, input| generic_arg(builder, input, nesting),
+            B::type_arg,
+        )?;
+        builder.push_generic_arg(&mut type_params, generic_arg);
+        Ok((input, ()))
+    });
+
+    match tag(remaining, ">") {
+        Ok((remaining, _)) => (remaining, Some(type_params)),
+        Err(_) => {
+            builder.rollback(checkpoint);
+            (input, None)
+        }
+    }
+}
+
+/// Parses the `; N` length of an array, if present.
+pub fn array_length(input: &str) -> (&str, Option<&str>) {
+    match tag(input, "; ").and_then(|(remaining, _)| take_until(remaining, "]")) {
+        Ok((remaining, len)) => (remaining, Some(len)),
+        Err(_) => (input, None),
+    }
+}
+
+pub fn array_or_slice_internal<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    let (input, type_param) = list_element(
+        builder,
+        input,
+        |builder, input| type_name(builder, input, nesting),
+        |_, type_name| type_name,
+    )?;
+    let (input, len) = array_length(input);
+
+    if let Some(len) = len {
+        Ok((input, builder.array(type_param, len)))
     } else {
-        struct_type(input)
-            .map(|(input, type_name_struct)| (input, TypeName::Struct(type_name_struct)))
+        Ok((input, builder.slice(type_param)))
     }
 }
 
-pub fn trait_type(input: &str) -> IResult<&str, TypeName<'_>> {
-    struct_type(input).map(|(input, type_name_struct)| {
-        (
+pub fn array_or_slice<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    let (input, _) = tag(input, "[")?;
+    let (input, type_name) = array_or_slice_internal(builder, input, nesting)?;
+    let (input, _) = tag(input, "]")?;
+
+    Ok((input, type_name))
+}
+
+pub fn lifetime(input: &str) -> ParseResult<'_, &str> {
+    let (remaining, _) = tag(input, "'")?;
+    let (remaining, _) = identifier(remaining)?;
+
+    Ok((remaining, consumed(input, remaining)))
+}
+
+pub fn parse_reference<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    let (input, _) = tag(input, "&")?;
+    let (input, lifetime) = match lifetime(input) {
+        Ok((remaining, lifetime)) => match tag(remaining, " ") {
+            Ok((remaining, _)) => (remaining, Some(lifetime)),
+            Err(_) => (input, None),
+        },
+        Err(_) => (input, None),
+    };
+    let (input, mutable) = match tag(input, "mut ") {
+        Ok((remaining, _)) => (remaining, true),
+        Err(_) => (input, false),
+    };
+    let (input, type_param) = type_name(builder, input, nesting)?;
+
+    Ok((input, builder.reference(lifetime, mutable, type_param)))
+}
+
+pub fn parse_pointer<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    let (input, _) = tag(input, "*")?;
+    let (input, const_or_mut) = tag(input, "const").or_else(|_| tag(input, "mut"))?;
+    let (input, _) = tag(input, " ")?;
+    let (input, type_param) = type_name(builder, input, nesting)?;
+
+    Ok((input, builder.pointer(const_or_mut, type_param)))
+}
+
+/// Parses the parameters of a function pointer, up to but excluding the
+/// closing `)`.
+///
+/// Returns the parameter types, and whether the function is variadic.
+pub fn function_params<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> (&'s str, (B::Types, bool))
+where
+    B: Builder<'s>,
+{
+    let mut params = builder.types();
+    if input.starts_with(')') {
+        return (input, (params, false));
+    }
+
+    // `None` represents the `...` of a variadic function, which is only
+    // variadic when the `...` is the last parameter.
+    let mut variadic = false;
+    let input = separated_list0(builder, input, ", ", |builder, input| {
+        let (input, param) = list_element(
+            builder,
             input,
-            TypeName::Trait(TypeNameTrait {
-                inner: type_name_struct,
-            }),
-        )
-    })
+            |builder, input| match tag(input, "...") {
+                Ok((input, _)) => Ok((input, None)),
+                Err(_) => type_name(builder, input, nesting)
+                    .map(|(input, type_name)| (input, Some(type_name))),
+            },
+            |_, type_name| Some(type_name),
+        )?;
+
+        variadic = param.is_none();
+        if let Some(param) = param {
+            builder.push_type(&mut params, param);
+        }
+        Ok((input, ()))
+    });
+
+    (input, (params, variadic))
+}
+
+/// Parses a higher-ranked lifetime binder, e.g. `for<'a, 'b> `.
+pub fn for_lifetimes<'s, B>(builder: &mut B, input: &'s str) -> ParseResult<'s, B::Lifetimes>
+where
+    B: Builder<'s>,
+{
+    let (input, _) = tag(input, "for<")?;
+    let mut lifetimes = builder.lifetimes();
+    let (input, ()) = separated_list1(builder, input, ", ", |builder, input| {
+        let (input, lifetime) = lifetime(input)?;
+        builder.push_lifetime(&mut lifetimes, lifetime);
+        Ok((input, ()))
+    })?;
+    let (input, _) = tag(input, "> ")?;
+
+    Ok((input, lifetimes))
+}
+
+/// Parses a higher-ranked lifetime binder if present, otherwise returns an
+/// empty list of lifetimes.
+fn optional_for_lifetimes<'s, B>(builder: &mut B, input: &'s str) -> (&'s str, B::Lifetimes)
+where
+    B: Builder<'s>,
+{
+    match attempt(builder, |builder| for_lifetimes(builder, input)) {
+        Ok((input, bound_lifetimes)) => (input, bound_lifetimes),
+        Err(_) => (input, builder.lifetimes()),
+    }
+}
+
+pub fn parse_function<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    let (input, bound_lifetimes) = optional_for_lifetimes(builder, input);
+    let (input, is_unsafe) = match tag(input, "unsafe ") {
+        Ok((input, _)) => (input, true),
+        Err(_) => (input, false),
+    };
+    let abi = tag(input, "extern \"")
+        .and_then(|(input, _)| take_until(input, "\""))
+        .and_then(|(input, abi)| tag(input, "\" ").map(|(input, _)| (input, abi)));
+    let (input, abi) = match abi {
+        Ok((input, abi)) => (input, Some(abi)),
+        Err(_) => (input, None),
+    };
+    let (input, _) = tag(input, "fn(")?;
+    let (input, (params, variadic)) = function_params(builder, input, nesting);
+    let (input, _) = tag(input, ")")?;
+    let (input, return_type) = return_type(builder, input, nesting);
+
+    Ok((
+        input,
+        builder.function(
+            bound_lifetimes,
+            is_unsafe,
+            abi,
+            params,
+            variadic,
+            return_type,
+        ),
+    ))
+}
+
+/// Parses the ` -> T` return type of a function, if present.
+fn return_type<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> (&'s str, Option<B::Type>)
+where
+    B: Builder<'s>,
+{
+    let return_type = attempt(builder, |builder| {
+        tag(input, " -> ").and_then(|(input, _)| type_name(builder, input, nesting))
+    });
+    match return_type {
+        Ok((input, type_name)) => (input, Some(type_name)),
+        Err(_) => (input, None),
+    }
+}
+
+/// Parses one or more `::` separated associated item names, e.g. `::IntoIter`,
+/// returning the path without the leading `::`.
+fn assoc_path(input: &str) -> ParseResult<'_, &str> {
+    let assoc_segment = |input| tag(input, "::").and_then(|(input, _)| identifier(input));
+    let (mut remaining, _) = assoc_segment(input)?;
+    while let Ok((rest, _)) = assoc_segment(remaining) {
+        remaining = rest;
+    }
+
+    Ok((remaining, &consumed(input, remaining)[2..]))
+}
+
+/// Parses a qualified path, e.g. `<alloc::vec::Vec<u8> as
+/// core::iter::IntoIterator>::IntoIter`.
+pub fn parse_qualified_path<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    let (input, _) = tag(input, "<")?;
+    let (input, self_type) = type_name(builder, input, nesting)?;
+    let trait_path = attempt(builder, |builder| {
+        tag(input, " as ").and_then(|(input, _)| struct_type(builder, input, nesting))
+    });
+    let (input, trait_path) = match trait_path {
+        Ok((input, trait_path)) => (input, Some(trait_path)),
+        Err(_) => (input, None),
+    };
+    let (input, _) = tag(input, ">")?;
+    let (input, assoc_path) = assoc_path(input)?;
+
+    // Closures in trait impl methods continue the path, e.g.
+    // `<a::Foo as a::Tr>::run::{{closure}}`, where the method may also have
+    // generic arguments, e.g. `<a::Foo as a::Tr>::run<u8>::{{closure}}`.
+    if input.starts_with('<') || input.starts_with("::") {
+        let checkpoint = builder.checkpoint();
+        let (remaining, type_params) = type_parameters(builder, input, nesting);
+        let closure = match type_params {
+            Some(type_params) if remaining.starts_with("::") => {
+                let (assoc_path, method_name) = split_last_segment(assoc_path);
+                let parent =
+                    builder.qualified_path(self_type.clone(), trait_path.clone(), assoc_path);
+                let method = builder.struct_path("", method_name, Some(type_params));
+                let method = builder.struct_type(method);
+
+                let mut parent_path = builder.types();
+                builder.push_type(&mut parent_path, parent);
+                builder.push_type(&mut parent_path, method);
+                closure_after_parents(builder, remaining, nesting, parent_path)
+            }
+            Some(_) => Err(remaining),
+            None => {
+                let parent =
+                    builder.qualified_path(self_type.clone(), trait_path.clone(), assoc_path);
+
+                let mut parent_path = builder.types();
+                builder.push_type(&mut parent_path, parent);
+                closure_after_parents(builder, remaining, nesting, parent_path)
+            }
+        };
+
+        match closure {
+            Ok(closure) => return Ok(closure),
+            Err(_) => builder.rollback(checkpoint),
+        }
+    }
+
+    Ok((
+        input,
+        builder.qualified_path(self_type, trait_path, assoc_path),
+    ))
+}
+
+pub fn parse_unit<'s, B>(builder: &mut B, input: &'s str) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    tag(input, "()").map(|(input, _)| (input, builder.unit()))
+}
+
+pub fn parse_tuple<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    let (input, _) = tag(input, "(")?;
+    let mut type_params = builder.types();
+    let input = separated_list0(builder, input, ", ", |builder, input| {
+        let (input, type_param) = list_element(
+            builder,
+            input,
+            |builder, input| type_name(builder, input, nesting),
+            |_, type_name| type_name,
+        )?;
+        builder.push_type(&mut type_params, type_param);
+        Ok((input, ()))
+    });
+    let input = input.strip_prefix(',').unwrap_or(input);
+    let (input, _) = tag(input, ")")?;
+
+    Ok((input, builder.tuple(type_params)))
+}
+
+/// Parses a trait object surrounded by parentheses, e.g. `(dyn A + Send)`.
+pub fn parse_parenthesized_trait<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    let (input, _) = tag(input, "(dyn ")?;
+    let (input, type_name) = trait_type(builder, input, nesting, true)?;
+    let (input, _) = tag(input, ")")?;
+
+    Ok((input, type_name))
+}
+
+pub fn parse_unit_or_tuple<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    if let Ok(parsed) = parse_unit(builder, input) {
+        return Ok(parsed);
+    }
+    if let Ok(parsed) = attempt(builder, |builder| {
+        parse_parenthesized_trait(builder, input, nesting)
+    }) {
+        return Ok(parsed);
+    }
+
+    parse_tuple(builder, input, nesting)
+}
+
+/// Parses a path followed by its generic arguments.
+pub fn struct_type<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::Struct>
+where
+    B: Builder<'s>,
+{
+    let (input, path) = path(input)?;
+    let (input, type_params) = type_parameters(builder, input, nesting);
+    let (module_path, simple_name) = split_last_segment(path);
+
+    Ok((
+        input,
+        builder.struct_path(module_path, simple_name, type_params),
+    ))
+}
+
+/// Returns the path of the function within an `{async fn body of a::b()}`
+/// segment.
+pub fn async_fn_body_path(segment: &str) -> &str {
+    segment
+        .strip_prefix("{async fn body of ")
+        .and_then(|path| path.split_once('('))
+        .map_or("", |(path, _)| path)
+}
+
+/// Parses the rest of a closure path that continues after the given parent
+/// path, e.g. `::run<u16>::{{closure}}` after `my_crate::Foo<u8>`.
+///
+/// Fails unless the path ends in a closure segment.
+///
+/// # Parameters
+///
+/// * `builder`: Builder of the parsed values.
+/// * `input`: Input that follows the parent path.
+/// * `nesting`: Nesting of the closure type.
+/// * `parent_path`: Qualified path or paths with generic arguments that the
+///   closure path begins with.
+fn closure_after_parents<'s, B>(
+    builder: &mut B,
+    mut input: &'s str,
+    nesting: Nesting,
+    mut parent_path: B::Types,
+) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    loop {
+        let (remaining, path) = tag(input, "::").and_then(|(input, _)| path(input))?;
+        let (remaining, type_params) = type_parameters(builder, remaining, nesting);
+        let (module_path, simple_name) = split_last_segment(path);
+
+        let Some(type_params) = type_params else {
+            let kind = closure_kind(simple_name).ok_or(input)?;
+            let type_name_closure =
+                builder.closure(kind, parent_path, module_path, simple_name, module_path);
+
+            return Ok((remaining, type_name_closure));
+        };
+
+        let parent = builder.struct_path(module_path, simple_name, Some(type_params));
+        let parent = builder.struct_type(parent);
+        builder.push_type(&mut parent_path, parent);
+        input = remaining;
+    }
+}
+
+/// Parses a type that is named by a path, e.g. `u32`, `alloc::vec::Vec<u8>`,
+/// or `my_crate::main::{{closure}}`.
+///
+/// Closures in generic functions and in methods of generic types continue
+/// the path after the generic arguments, e.g.
+/// `my_crate::Foo<u8>::run::{{closure}}`.
+pub fn path_type<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    let (input, path) = path(input)?;
+    let (module_path, simple_name) = split_last_segment(path);
+
+    if let Some(kind) = closure_kind(simple_name) {
+        // Closure segments do not have generic arguments, so any that follow
+        // are discarded.
+        let checkpoint = builder.checkpoint();
+        let (input, _) = type_parameters(builder, input, nesting);
+        builder.rollback(checkpoint);
+
+        let enclosing_path = if module_path.is_empty() {
+            async_fn_body_path(simple_name)
+        } else {
+            module_path
+        };
+        let parent_path = builder.types();
+        let type_name_closure =
+            builder.closure(kind, parent_path, module_path, simple_name, enclosing_path);
+        return Ok((input, type_name_closure));
+    }
+
+    let (input, type_params) = type_parameters(builder, input, nesting);
+    let has_type_params = type_params.is_some();
+    let parent = builder.struct_path(module_path, simple_name, type_params);
+    let parent = builder.struct_type(parent);
+    if !has_type_params || !input.starts_with("::") {
+        return Ok((input, parent));
+    }
+
+    let checkpoint = builder.checkpoint();
+    let mut parent_path = builder.types();
+    builder.push_type(&mut parent_path, parent.clone());
+    match closure_after_parents(builder, input, nesting, parent_path) {
+        Ok(closure) => Ok(closure),
+        Err(_) => {
+            builder.rollback(checkpoint);
+            Ok((input, parent))
+        }
+    }
+}
+
+/// Parses the parenthesized arguments of `Fn`-like traits, e.g. `(&'_ str) ->
+/// u32`.
+pub fn fn_sugar<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, (B::Types, Option<B::Type>)>
+where
+    B: Builder<'s>,
+{
+    let (input, _) = tag(input, "(")?;
+    let (input, (params, _variadic)) = function_params(builder, input, nesting);
+    let (input, _) = tag(input, ")")?;
+    let (input, return_type) = return_type(builder, input, nesting);
+
+    Ok((input, (params, return_type)))
+}
+
+/// Parses a trait bound, e.g. `core::fmt::Debug` or `for<'a>
+/// core::ops::function::Fn(&'a str) -> u32`.
+pub fn trait_bound<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::TraitBound>
+where
+    B: Builder<'s>,
+{
+    let (input, bound_lifetimes) = optional_for_lifetimes(builder, input);
+    let (input, inner) = struct_type(builder, input, nesting)?;
+    let (input, (fn_params, fn_return_type)) =
+        match attempt(builder, |builder| fn_sugar(builder, input, nesting)) {
+            Ok((input, (params, return_type))) => (input, (Some(params), return_type)),
+            Err(_) => (input, (None, None)),
+        };
+
+    Ok((
+        input,
+        builder.trait_bound(bound_lifetimes, inner, fn_params, fn_return_type),
+    ))
+}
+
+/// Parses the bounds of a trait object, e.g. `core::error::Error +
+/// core::marker::Send + 'static`.
+///
+/// `parenthesized` is whether the trait object is surrounded by parentheses,
+/// e.g. `&(dyn A + Send)`.
+pub fn trait_type<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+    parenthesized: bool,
+) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    let mut bounds = builder.trait_bounds();
+    let mut lifetime_bounds = builder.lifetimes();
+    let mut has_trait_bound = false;
+    let (remaining, ()) = separated_list1(builder, input, " + ", |builder, input| {
+        if let Ok((input, lifetime)) = lifetime(input) {
+            builder.push_lifetime(&mut lifetime_bounds, lifetime);
+            return Ok((input, ()));
+        }
+
+        let (input, bound) = trait_bound(builder, input, nesting)?;
+        builder.push_trait_bound(&mut bounds, bound);
+        has_trait_bound = true;
+        Ok((input, ()))
+    })?;
+
+    // Trait objects have at least one trait bound, so `dyn 'static` is not a
+    // trait object.
+    if !has_trait_bound {
+        return Err(input);
+    }
+    let input = remaining;
+
+    Ok((
+        input,
+        builder.trait_object(bounds, lifetime_bounds, parenthesized),
+    ))
 }
 
 /// Parses a type name.
-pub fn type_name(input: &str) -> IResult<&str, TypeName<'_>> {
-    // Primitive types begin with lowercase letters, but we have to detect them at
-    // this level, as lower parsers (`module_name`, `type_simple_name`) cannot
-    // tell if `"std::"` precedes the input it is given.
-    //
-    // In addition, types may begin with symbols, and we should detect them here and
-    // branch to the relevant parsing functions.
+///
+/// Types that exceed the parse limits are captured verbatim as elided types,
+/// without recursing into them.
+pub fn type_name<'s, B>(
+    builder: &mut B,
+    input: &'s str,
+    nesting: Nesting,
+) -> ParseResult<'s, B::Type>
+where
+    B: Builder<'s>,
+{
+    if nesting.is_exceeded(input) {
+        return match unknown(input) {
+            Ok((input, text)) => Ok((input, builder.elided(text))),
+            Err(input) => Ok((input, builder.none())),
+        };
+    }
+    let nesting = nesting.nested();
+
+    // Types may begin with symbols or keywords, and we should detect them here and
+    // branch to the relevant parsing functions. Everything else, including
+    // primitive types, is named by a path.
     let mut chars = input.chars();
     if let Some(first_char) = chars.next() {
         match first_char {
-            '[' => array_or_slice(input),
-            '*' => unimplemented!("`tynm` is not implemented for pointer types."),
-            '!' => nom::character::complete::char('!')
-                .parse(input)
-                .map(|(input, _)| (input, TypeName::Never)),
-            '&' => parse_reference(input),
-            '(' => parse_unit_or_tuple(input),
+            '[' => array_or_slice(builder, input, nesting),
+            '*' => parse_pointer(builder, input, nesting),
+            '!' => tag(input, "!").map(|(input, _)| (input, builder.never())),
+            '&' => parse_reference(builder, input, nesting),
+            '(' => parse_unit_or_tuple(builder, input, nesting),
+            '<' => parse_qualified_path(builder, input, nesting),
+            'e' | 'f' | 'u'
+                if input.starts_with("fn(")
+                    || input.starts_with("for<")
+                    || input.starts_with("unsafe ")
+                    || input.starts_with("extern \"") =>
+            {
+                parse_function(builder, input, nesting)
+            }
             'd' => {
                 let mut split = input.splitn(2, ' ');
                 if let Some("dyn") = split.next() {
                     if let Some(remainder) = split.next() {
-                        trait_type(remainder)
+                        trait_type(builder, remainder, nesting, false)
                     } else {
                         // We only have "dyn" as a token. User may have specified r#dyn as a struct
                         // name or function name, but this is unusual. For
                         // now, we treat it as a struct.
-                        Ok((
-                            "",
-                            TypeName::Struct(TypeNameStruct {
-                                module_path: vec![],
-                                simple_name: "dyn",
-                                type_params: vec![],
-                            }),
-                        ))
+                        let type_name_struct = builder.struct_path("", &input[..3], None);
+                        Ok(("", builder.struct_type(type_name_struct)))
                     }
                 } else {
-                    named_primitive_or_struct(input)
+                    path_type(builder, input, nesting)
                 }
             }
-            _ => named_primitive_or_struct(input),
+            _ => path_type(builder, input, nesting),
         }
     } else {
-        Ok((input, TypeName::None))
+        Ok((input, builder.none()))
     }
 }
@@ -0,0 +1,560 @@
+use core::fmt::{Error, Write};
+
+use crate::{closure_kind::ClosureKind, stream, type_name_fmt::TypeNameFmt, TypeParamsFmtOpts};
+
+/// A node of a type name that can be rendered, which is implemented for both
+/// the [`TypeName`] tree and the nodes of a [`TypeNameFlat`].
+///
+/// Type names are rendered from [`NodeView`]s by [`write_node`], so that each
+/// formatting option is implemented once for every representation.
+///
+/// [`TypeName`]: crate::TypeName
+/// [`TypeNameFlat`]: crate::TypeNameFlat
+pub(crate) trait RenderNode<'a>: Copy {
+    /// Iterator over child nodes, e.g. the type parameters of a struct.
+    type Nodes: ExactSizeIterator<Item = Self> + Clone;
+    /// Iterator over path segments or lifetimes.
+    type Segments: ExactSizeIterator<Item = &'a str> + Clone;
+
+    /// Returns the contents of this node.
+    fn view(self) -> NodeView<'a, Self>;
+}
+
+/// State of a render that is not set by the format options.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct RenderContext {
+    /// Whether closures and elided types are written as their original text,
+    /// which reproduces the `core::any::type_name` string.
+    pub(crate) verbatim: bool,
+    /// Number of type parameter levels that the type being written is nested
+    /// in.
+    type_params_depth: usize,
+}
+
+impl RenderContext {
+    /// Context that reproduces the `core::any::type_name` string.
+    pub(crate) const VERBATIM: Self = Self {
+        verbatim: true,
+        type_params_depth: 0,
+    };
+
+    /// Returns this context for writing the type parameters of the type being
+    /// written.
+    fn nested(self) -> Self {
+        Self {
+            type_params_depth: self.type_params_depth + 1,
+            ..self
+        }
+    }
+}
+
+/// Contents of a node of a type name.
+///
+/// This mirrors [`TypeName`] and [`GenericArg`], with the addition of
+/// `TraitBound` for each bound of a trait object.
+///
+/// [`TypeName`]: crate::TypeName
+/// [`GenericArg`]: crate::GenericArg
+pub(crate) enum NodeView<'a, N: RenderNode<'a>> {
+    None,
+    Array {
+        type_param: N,
+        len: &'a str,
+    },
+    Closure {
+        kind: ClosureKind,
+        parent_path: N::Nodes,
+        module_path: N::Segments,
+        segment: &'a str,
+        enclosing_path: N::Segments,
+    },
+    Function {
+        bound_lifetimes: N::Segments,
+        is_unsafe: bool,
+        abi: Option<&'a str>,
+        params: N::Nodes,
+        variadic: bool,
+        return_type: Option<N>,
+    },
+    Never,
+    Pointer {
+        const_or_mut: &'a str,
+        type_param: N,
+    },
+    QualifiedPath {
+        self_type: N,
+        /// A `Struct` node.
+        trait_path: Option<N>,
+        assoc_path: N::Segments,
+    },
+    Reference {
+        lifetime: Option<&'a str>,
+        mutable: bool,
+        type_param: N,
+    },
+    Slice {
+        type_param: N,
+    },
+    Struct {
+        module_path: N::Segments,
+        simple_name: &'a str,
+        type_params: N::Nodes,
+    },
+    Tuple {
+        type_params: N::Nodes,
+    },
+    Trait {
+        /// `TraitBound` nodes.
+        bounds: N::Nodes,
+        lifetime_bounds: N::Segments,
+        parenthesized: bool,
+    },
+    TraitBound {
+        bound_lifetimes: N::Segments,
+        /// A `Struct` node.
+        inner: N,
+        fn_params: Option<N::Nodes>,
+        fn_return_type: Option<N>,
+    },
+    Unit,
+    Unknown(&'a str),
+    Elided(&'a str),
+    Const(&'a str),
+    Lifetime(&'a str),
+    Binding {
+        name: &'a str,
+        type_name: N,
+    },
+}
+
+/// Writes the type name string of the node to the given buffer using the
+/// given format options.
+pub(crate) fn write_node<'a, N, W>(node: N, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+where
+    N: RenderNode<'a>,
+    W: Write,
+{
+    write_node_with(node, buffer, fmt, RenderContext::default())
+}
+
+/// Writes the type name string of the node to the given buffer using the
+/// given format options and render state.
+pub(crate) fn write_node_with<'a, N, W>(
+    node: N,
+    buffer: &mut W,
+    fmt: &TypeNameFmt,
+    context: RenderContext,
+) -> Result<(), Error>
+where
+    N: RenderNode<'a>,
+    W: Write,
+{
+    match node.view() {
+        NodeView::None => Ok(()),
+        NodeView::Array { type_param, len } => {
+            buffer.write_str("[")?;
+            write_node_with(type_param, buffer, fmt, context)?;
+            buffer.write_str("; ")?;
+            buffer.write_str(len)?;
+            buffer.write_str("]")
+        }
+        NodeView::Closure {
+            kind,
+            parent_path,
+            module_path,
+            segment,
+            enclosing_path,
+        } => {
+            if context.verbatim {
+                parent_path.clone().try_for_each(|parent| {
+                    write_node_with(parent, buffer, fmt, context)?;
+                    buffer.write_str("::")
+                })?;
+                module_path.clone().try_for_each(|segment| {
+                    buffer.write_str(segment)?;
+                    buffer.write_str("::")
+                })?;
+                return buffer.write_str(segment);
+            }
+
+            write_closure::<N, W>(buffer, kind, parent_path, enclosing_path, fmt, context)
+        }
+        NodeView::Function {
+            bound_lifetimes,
+            is_unsafe,
+            abi,
+            params,
+            variadic,
+            return_type,
+        } => {
+            write_bound_lifetimes(buffer, bound_lifetimes)?;
+            if is_unsafe {
+                buffer.write_str("unsafe ")?;
+            }
+            if let Some(abi) = abi {
+                buffer.write_str("extern \"")?;
+                buffer.write_str(abi)?;
+                buffer.write_str("\" ")?;
+            }
+
+            buffer.write_str("fn(")?;
+            let has_params = params.len() > 0;
+            write_fn_params::<N, W>(buffer, params, fmt, context)?;
+            if variadic {
+                if has_params {
+                    buffer.write_str(", ")?;
+                }
+                buffer.write_str("...")?;
+            }
+            buffer.write_str(")")?;
+
+            if let Some(return_type) = return_type {
+                buffer.write_str(" -> ")?;
+                write_node_with(return_type, buffer, fmt, context)?;
+            }
+
+            Ok(())
+        }
+        NodeView::Never => buffer.write_str("!"),
+        NodeView::Pointer {
+            const_or_mut,
+            type_param,
+        } => {
+            buffer.write_str("*")?;
+            buffer.write_str(const_or_mut)?;
+            buffer.write_str(" ")?;
+            write_node_with(type_param, buffer, fmt, context)
+        }
+        NodeView::QualifiedPath {
+            self_type,
+            trait_path,
+            assoc_path,
+        } => {
+            buffer.write_str("<")?;
+            write_node_with(self_type, buffer, fmt, context)?;
+            if let Some(trait_path) = trait_path {
+                buffer.write_str(" as ")?;
+                write_node_with(trait_path, buffer, fmt, context)?;
+            }
+            buffer.write_str(">")?;
+
+            assoc_path.into_iter().try_for_each(|segment| {
+                buffer.write_str("::")?;
+                buffer.write_str(segment)
+            })
+        }
+        NodeView::Reference {
+            lifetime,
+            mutable,
+            type_param,
+        } => {
+            buffer.write_str("&")?;
+            if let Some(lifetime) = lifetime {
+                buffer.write_str(lifetime)?;
+                buffer.write_str(" ")?;
+            }
+            if mutable {
+                buffer.write_str("mut ")?;
+            }
+            write_node_with(type_param, buffer, fmt, context)
+        }
+        NodeView::Slice { type_param } => {
+            // Don't need to prepend with `"&"` because slices are always passed in
+            // as references.
+            buffer.write_str("[")?;
+            write_node_with(type_param, buffer, fmt, context)?;
+            buffer.write_str("]")
+        }
+        NodeView::Struct {
+            module_path,
+            simple_name,
+            type_params,
+        } => write_struct::<N, W>(buffer, module_path, simple_name, type_params, fmt, context),
+        NodeView::Tuple { type_params } => {
+            buffer.write_str("(")?;
+
+            let is_lone = type_params.len() == 1;
+            let elided = write_type_params::<N, W>(buffer, type_params, fmt, context)?;
+            // Always write `,` after a lone type.
+            if is_lone && !elided {
+                buffer.write_str(",")?;
+            }
+
+            buffer.write_str(")")
+        }
+        NodeView::Trait {
+            bounds,
+            lifetime_bounds,
+            parenthesized,
+        } => {
+            if parenthesized {
+                buffer.write_str("(")?;
+            }
+            buffer.write_str("dyn ")?;
+
+            // The principal trait is always written, even if it is an auto
+            // trait.
+            let mut bounds = bounds.enumerate().filter(|(index, bound)| {
+                *index == 0
+                    || !struct_path(*bound).is_some_and(|(mut module_path, simple_name)| {
+                        fmt.is_elided_auto_trait(module_path.next(), simple_name)
+                    })
+            });
+            let mut separator = "";
+            bounds.try_for_each(|(_, bound)| {
+                buffer.write_str(separator)?;
+                separator = " + ";
+                write_node_with(bound, buffer, fmt, context)
+            })?;
+            lifetime_bounds.into_iter().try_for_each(|lifetime| {
+                buffer.write_str(separator)?;
+                separator = " + ";
+                buffer.write_str(lifetime)
+            })?;
+
+            if parenthesized {
+                buffer.write_str(")")?;
+            }
+
+            Ok(())
+        }
+        NodeView::TraitBound {
+            bound_lifetimes,
+            inner,
+            fn_params,
+            fn_return_type,
+        } => {
+            write_bound_lifetimes(buffer, bound_lifetimes)?;
+            write_node_with(inner, buffer, fmt, context)?;
+
+            if let Some(fn_params) = fn_params {
+                buffer.write_str("(")?;
+                write_fn_params::<N, W>(buffer, fn_params, fmt, context)?;
+                buffer.write_str(")")?;
+
+                if let Some(fn_return_type) = fn_return_type {
+                    buffer.write_str(" -> ")?;
+                    write_node_with(fn_return_type, buffer, fmt, context)?;
+                }
+            }
+
+            Ok(())
+        }
+        NodeView::Unit => buffer.write_str("()"),
+        NodeView::Elided(_) if !context.verbatim => buffer.write_str(".."),
+        NodeView::Unknown(text)
+        | NodeView::Elided(text)
+        | NodeView::Const(text)
+        | NodeView::Lifetime(text) => buffer.write_str(text),
+        NodeView::Binding { name, type_name } => {
+            buffer.write_str(name)?;
+            buffer.write_str(" = ")?;
+            write_node_with(type_name, buffer, fmt, context)
+        }
+    }
+}
+
+/// Returns the module path and simple name of a struct node, or of the trait
+/// of a trait bound node.
+fn struct_path<'a, N>(node: N) -> Option<(N::Segments, &'a str)>
+where
+    N: RenderNode<'a>,
+{
+    match node.view() {
+        NodeView::Struct {
+            module_path,
+            simple_name,
+            ..
+        } => Some((module_path, simple_name)),
+        NodeView::TraitBound { inner, .. } => struct_path(inner),
+        _ => None,
+    }
+}
+
+/// Writes a struct's module path, simple name, and type parameters.
+fn write_struct<'a, N, W>(
+    buffer: &mut W,
+    module_path: N::Segments,
+    simple_name: &str,
+    type_params: N::Nodes,
+    fmt: &TypeNameFmt,
+    context: RenderContext,
+) -> Result<(), Error>
+where
+    N: RenderNode<'a>,
+    W: Write,
+{
+    match fmt.public_module_path(module_path.clone(), simple_name) {
+        Some(public_module_path) => stream::write_module_path(
+            buffer,
+            public_module_path.iter().copied(),
+            public_module_path.len(),
+            fmt,
+        )?,
+        None => stream::write_module_path(buffer, module_path.clone(), module_path.len(), fmt)?,
+    }
+    buffer.write_str(simple_name)?;
+
+    let type_params_shown = match fmt.type_params_fmt_opts {
+        TypeParamsFmtOpts::None => false,
+        TypeParamsFmtOpts::Std => module_path
+            .clone()
+            .next()
+            .is_some_and(|crate_name| fmt.is_std_crate(crate_name)),
+        _ => true,
+    };
+    if type_params_shown {
+        write_angle_type_params::<N, W>(buffer, type_params, fmt, context)?;
+    }
+
+    Ok(())
+}
+
+/// Writes angle bracketed type parameters to the given buffer, e.g. `<u8>`.
+///
+/// Nothing is written if there are no type parameters.
+pub(crate) fn write_angle_type_params<'a, N, W>(
+    buffer: &mut W,
+    type_params: N::Nodes,
+    fmt: &TypeNameFmt,
+    context: RenderContext,
+) -> Result<(), Error>
+where
+    N: RenderNode<'a>,
+    W: Write,
+{
+    if type_params.len() > 0 {
+        buffer.write_str("<")?;
+        write_type_params::<N, W>(buffer, type_params, fmt, context)?;
+        buffer.write_str(">")?;
+    }
+
+    Ok(())
+}
+
+/// Writes the short form of a closure's name, e.g. `{closure in main}`.
+///
+/// # Parameters
+///
+/// * `buffer`: Buffer to write to.
+/// * `kind`: Kind of closure.
+/// * `parent_path`: Leading parts of the closure's path that are not plain
+///   segments.
+/// * `enclosing_path`: Path of the item that the closure is defined in, after
+///   the parent path.
+/// * `fmt`: Format options for the module path.
+fn write_closure<'a, N, W>(
+    buffer: &mut W,
+    kind: ClosureKind,
+    mut parent_path: N::Nodes,
+    enclosing_path: N::Segments,
+    fmt: &TypeNameFmt,
+    context: RenderContext,
+) -> Result<(), Error>
+where
+    N: RenderNode<'a>,
+    W: Write,
+{
+    // Generic arguments of the parent path are not written, as they are not
+    // part of the item's path.
+    let qualified_path = parent_path
+        .clone()
+        .next()
+        .filter(|parent| matches!(parent.view(), NodeView::QualifiedPath { .. }));
+    if qualified_path.is_some() {
+        parent_path.next();
+    }
+    let item_path = parent_path
+        .filter_map(|parent| struct_path(parent))
+        .flat_map(|(module_path, simple_name)| module_path.chain(core::iter::once(simple_name)))
+        .chain(enclosing_path);
+
+    stream::write_closure_name(
+        buffer,
+        kind,
+        qualified_path.map(|qualified_path| {
+            move |buffer: &mut W| write_node_with(qualified_path, buffer, fmt, context)
+        }),
+        item_path,
+        fmt,
+    )
+}
+
+/// Writes higher-ranked lifetimes to the given buffer, e.g. `for<'a> `.
+fn write_bound_lifetimes<'l, W>(
+    buffer: &mut W,
+    mut bound_lifetimes: impl Iterator<Item = &'l str>,
+) -> Result<(), Error>
+where
+    W: Write,
+{
+    if let Some(first) = bound_lifetimes.next() {
+        buffer.write_str("for<")?;
+        buffer.write_str(first)?;
+        bound_lifetimes.try_for_each(|lifetime| {
+            buffer.write_str(", ")?;
+            buffer.write_str(lifetime)
+        })?;
+        buffer.write_str("> ")?;
+    }
+
+    Ok(())
+}
+
+/// Writes comma separated type parameters to the given buffer, eliding them
+/// according to the type parameter formatting options.
+///
+/// Returns whether any type parameters were elided.
+fn write_type_params<'a, N, W>(
+    buffer: &mut W,
+    type_params: N::Nodes,
+    fmt: &TypeNameFmt,
+    context: RenderContext,
+) -> Result<bool, Error>
+where
+    N: RenderNode<'a>,
+    W: Write,
+{
+    let (shown_count, marker) = fmt
+        .type_params_fmt_opts
+        .type_params_shown(context.type_params_depth, type_params.len());
+
+    let context = context.nested();
+    type_params
+        .take(shown_count)
+        .enumerate()
+        .try_for_each(|(index, type_param)| {
+            if index > 0 {
+                buffer.write_str(", ")?;
+            }
+            write_node_with(type_param, buffer, fmt, context)
+        })?;
+
+    if let Some(marker) = marker {
+        if shown_count > 0 {
+            buffer.write_str(", ")?;
+        }
+        buffer.write_str(marker)?;
+    }
+
+    Ok(marker.is_some())
+}
+
+/// Writes comma separated function parameters to the given buffer.
+fn write_fn_params<'a, N, W>(
+    buffer: &mut W,
+    params: N::Nodes,
+    fmt: &TypeNameFmt,
+    context: RenderContext,
+) -> Result<(), Error>
+where
+    N: RenderNode<'a>,
+    W: Write,
+{
+    params.enumerate().try_for_each(|(index, param)| {
+        if index > 0 {
+            buffer.write_str(", ")?;
+        }
+        write_node_with(param, buffer, fmt, context)
+    })
+}
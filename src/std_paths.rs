@@ -0,0 +1,227 @@
+/// Types that are re-exported from a different public module than the other
+/// items of their definition module.
+///
+/// Each entry is the definition module, the type's name, and the public module.
+const TYPE_PATHS: &[(&str, &str, &[&str])] = &[
+    (
+        "std::collections::binary_heap",
+        "BinaryHeap",
+        &["std", "collections"],
+    ),
+    (
+        "std::collections::btree::map",
+        "BTreeMap",
+        &["std", "collections"],
+    ),
+    (
+        "std::collections::btree::set",
+        "BTreeSet",
+        &["std", "collections"],
+    ),
+    (
+        "std::collections::hash::map",
+        "HashMap",
+        &["std", "collections"],
+    ),
+    (
+        "std::collections::hash::set",
+        "HashSet",
+        &["std", "collections"],
+    ),
+    (
+        "std::collections::linked_list",
+        "LinkedList",
+        &["std", "collections"],
+    ),
+    (
+        "std::collections::vec_deque",
+        "VecDeque",
+        &["std", "collections"],
+    ),
+];
+
+/// Definition modules, and the public modules that re-export their items.
+///
+/// An entry applies to its module and all of its submodules. When several
+/// entries apply, the one with the longest definition module is used, so
+/// public submodules are listed to keep them from being shortened.
+const MODULE_PATHS: &[(&str, &[&str])] = &[
+    ("std::alloc", &["std", "alloc"]),
+    ("std::array", &["std", "array"]),
+    ("std::ascii", &["std", "ascii"]),
+    ("std::boxed", &["std", "boxed"]),
+    ("std::cell", &["std", "cell"]),
+    ("std::char", &["std", "char"]),
+    (
+        "std::collections::binary_heap",
+        &["std", "collections", "binary_heap"],
+    ),
+    (
+        "std::collections::btree::map",
+        &["std", "collections", "btree_map"],
+    ),
+    (
+        "std::collections::btree::set",
+        &["std", "collections", "btree_set"],
+    ),
+    (
+        "std::collections::hash::map",
+        &["std", "collections", "hash_map"],
+    ),
+    (
+        "std::collections::hash::set",
+        &["std", "collections", "hash_set"],
+    ),
+    (
+        "std::collections::linked_list",
+        &["std", "collections", "linked_list"],
+    ),
+    (
+        "std::collections::vec_deque",
+        &["std", "collections", "vec_deque"],
+    ),
+    ("std::convert", &["std", "convert"]),
+    ("std::ffi", &["std", "ffi"]),
+    ("std::fmt", &["std", "fmt"]),
+    ("std::future", &["std", "future"]),
+    ("std::hash", &["std", "hash"]),
+    ("std::io", &["std", "io"]),
+    ("std::iter", &["std", "iter"]),
+    ("std::mem", &["std", "mem"]),
+    ("std::net", &["std", "net"]),
+    ("std::num", &["std", "num"]),
+    ("std::ops", &["std", "ops"]),
+    ("std::os::fd", &["std", "os", "fd"]),
+    ("std::os::unix::ffi", &["std", "os", "unix", "ffi"]),
+    ("std::os::unix::net", &["std", "os", "unix", "net"]),
+    ("std::os::windows::ffi", &["std", "os", "windows", "ffi"]),
+    ("std::os::windows::io", &["std", "os", "windows", "io"]),
+    ("std::panic", &["std", "panic"]),
+    ("std::pin", &["std", "pin"]),
+    ("std::ptr", &["std", "ptr"]),
+    ("std::slice", &["std", "slice"]),
+    ("std::str", &["std", "str"]),
+    ("std::str::pattern", &["std", "str", "pattern"]),
+    ("std::string", &["std", "string"]),
+    ("std::sync", &["std", "sync"]),
+    ("std::sync::atomic", &["std", "sync", "atomic"]),
+    ("std::sync::mpsc", &["std", "sync", "mpsc"]),
+    ("std::task", &["std", "task"]),
+    ("std::thread", &["std", "thread"]),
+    ("std::time", &["std", "time"]),
+    ("std::vec", &["std", "vec"]),
+];
+
+/// Returns the public module path that a standard library type is re-exported
+/// from, if it differs from the type's definition module.
+///
+/// `core::any::type_name` reports the module that a type is defined in, which
+/// is often private, e.g. `std::collections::hash::map` for `HashMap`, which is
+/// re-exported from `std::collections`. The tables list definition modules
+/// under `std`, as `core` and `alloc` modules are re-exported under the same
+/// path in `std`.
+///
+/// Returns `None` for types outside of `std`, `core`, and `alloc`, and for
+/// modules without an entry, which are public already.
+///
+/// # Parameters
+///
+/// * `module_path`: Module path segments that the type is defined in.
+/// * `simple_name`: Name of the type.
+pub(crate) fn public_module_path<'p, I>(
+    module_path: I,
+    simple_name: &str,
+) -> Option<&'static [&'static str]>
+where
+    I: IntoIterator<Item = &'p str>,
+    I::IntoIter: Clone,
+{
+    let mut module_path = module_path.into_iter();
+    if !matches!(module_path.next(), Some("std" | "core" | "alloc")) {
+        return None;
+    }
+
+    let type_path = TYPE_PATHS
+        .iter()
+        .find(|(definition_path, name, _)| {
+            *name == simple_name && module_path.clone().eq(segments(definition_path))
+        })
+        .map(|(_, _, public_path)| *public_path);
+
+    type_path.or_else(|| {
+        MODULE_PATHS
+            .iter()
+            .filter(|(definition_path, _)| {
+                let mut module_path = module_path.clone();
+                segments(definition_path).all(|segment| module_path.next() == Some(segment))
+            })
+            .max_by_key(|(definition_path, _)| definition_path.len())
+            .map(|(_, public_path)| *public_path)
+    })
+}
+
+/// Returns the segments of a definition module, after the crate name.
+fn segments(definition_path: &'static str) -> impl Iterator<Item = &'static str> {
+    definition_path.split("::").skip(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{public_module_path, MODULE_PATHS, TYPE_PATHS};
+
+    fn public(module_path: &str, simple_name: &str) -> Option<&'static [&'static str]> {
+        public_module_path(module_path.split("::"), simple_name)
+    }
+
+    #[test]
+    fn public_module_path_maps_definition_modules() {
+        assert_eq!(
+            Some(&["std", "collections"][..]),
+            public("std::collections::hash::map", "HashMap")
+        );
+        assert_eq!(
+            Some(&["std", "collections", "hash_map"][..]),
+            public("std::collections::hash::map", "Entry")
+        );
+        assert_eq!(
+            Some(&["std", "collections", "btree_map"][..]),
+            public("alloc::collections::btree::map::entry", "Entry")
+        );
+        assert_eq!(
+            Some(&["std", "ptr"][..]),
+            public("core::ptr::non_null", "NonNull")
+        );
+        assert_eq!(
+            Some(&["std", "iter"][..]),
+            public("core::iter::adapters::map", "Map")
+        );
+        assert_eq!(
+            Some(&["std", "sync"][..]),
+            public("std::sync::mutex", "Mutex")
+        );
+        assert_eq!(
+            Some(&["std", "sync", "atomic"][..]),
+            public("core::sync::atomic", "AtomicBool")
+        );
+    }
+
+    #[test]
+    fn public_module_path_ignores_other_crates() {
+        assert_eq!(None, public("my_crate::collections::hash::map", "HashMap"));
+        assert_eq!(None, public("my_crate::iter::adapters", "Map"));
+        assert_eq!(None, public("std", "Foo"));
+        assert_eq!(None, public("core::ffix", "Foo"));
+    }
+
+    #[test]
+    fn tables_use_std_definition_paths() {
+        TYPE_PATHS
+            .iter()
+            .map(|(definition_path, _, public_path)| (*definition_path, *public_path))
+            .chain(MODULE_PATHS.iter().copied())
+            .for_each(|(definition_path, public_path)| {
+                assert!(definition_path.starts_with("std::"), "{definition_path}");
+                assert_eq!(Some(&"std"), public_path.first(), "{definition_path}");
+            });
+    }
+}
@@ -0,0 +1,705 @@
+use core::fmt::{Error, Write};
+
+use crate::{
+    closure_kind::{closure_kind, ClosureKind},
+    TypeNameFmt, TypeParamsFmtOpts,
+};
+
+/// Maximum number of nested brackets that are shortened. Text nested more
+/// deeply is written verbatim, which bounds the recursion depth.
+const MAX_NESTING: usize = 64;
+
+/// Writes the shortened form of a `core::any::type_name` string to the given
+/// buffer, without parsing it into a `TypeName` tree.
+///
+/// The string is scanned from left to right. Paths are written as they are
+/// found, with their module segments shortened, and every other character is
+/// written verbatim, except for elided type parameters. Nothing is allocated.
+///
+/// # Parameters
+///
+/// * `buffer`: Buffer to write to.
+/// * `type_name_qualified`: Type name string to shorten.
+/// * `fmt`: Options for formatting the type name.
+pub(crate) fn write_type_name_str_with<W>(
+    buffer: &mut W,
+    type_name_qualified: &str,
+    fmt: &TypeNameFmt,
+) -> Result<(), Error>
+where
+    W: Write,
+{
+    let mut stream = Stream {
+        buffer,
+        type_name_qualified,
+        fmt,
+    };
+
+    // Unmatched closing brackets end the outermost level early, so they are
+    // written verbatim and scanning resumes after them.
+    let mut pos = 0;
+    while pos < type_name_qualified.len() {
+        pos = stream.write_level(pos, 0, 0, false)?;
+        if pos < type_name_qualified.len() {
+            stream.write_byte(pos)?;
+            pos += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// State for writing a shortened type name string.
+struct Stream<'b, 's, 'f, W> {
+    /// Buffer to write to.
+    buffer: &'b mut W,
+    /// Type name string to shorten.
+    type_name_qualified: &'s str,
+    /// Options for formatting the type name.
+    fmt: &'f TypeNameFmt,
+}
+
+impl<'s, W> Stream<'_, 's, '_, W>
+where
+    W: Write,
+{
+    /// Writes text from `pos` until a closing bracket that is not opened
+    /// within the text, and returns the position of that bracket.
+    ///
+    /// # Parameters
+    ///
+    /// * `pos`: Position to begin writing from.
+    /// * `depth`: Number of type parameter levels that the text is nested in.
+    /// * `nesting`: Number of brackets that the text is nested in.
+    /// * `stop_at_comma`: Whether to also stop at a `,` that is not nested
+    ///   within the text.
+    fn write_level(
+        &mut self,
+        mut pos: usize,
+        depth: usize,
+        nesting: usize,
+        stop_at_comma: bool,
+    ) -> Result<usize, Error> {
+        let bytes = self.type_name_qualified.as_bytes();
+
+        while pos < bytes.len() {
+            match bytes[pos] {
+                // The `>` in `->` does not close a bracket.
+                b'>' if pos > 0 && bytes[pos - 1] == b'-' => {
+                    self.write_byte(pos)?;
+                    pos += 1;
+                }
+                b'>' | b')' | b']' => return Ok(pos),
+                b',' if stop_at_comma => return Ok(pos),
+                b'<' => pos = self.write_qualified_path(pos, depth, nesting)?,
+                b'[' => pos = self.write_group(pos, depth, nesting, false)?,
+                b'(' => {
+                    let rest = &bytes[pos + 1..];
+                    if rest.first() == Some(&b')') {
+                        self.buffer.write_str("()")?;
+                        pos += 2;
+                    } else {
+                        // Parentheses around a trait object do not hold type
+                        // parameters, unlike tuples.
+                        let is_tuple = !rest.starts_with(b"dyn ");
+                        pos = self.write_group(pos, depth, nesting, is_tuple)?;
+                    }
+                }
+                // Auto traits after the principal trait of a trait object.
+                b' ' if bytes[pos..].starts_with(b" + ") && self.is_elided_auto_trait(pos + 3) => {
+                    pos = path_end(bytes, pos + 3);
+                }
+                byte if is_delimiter(byte) => {
+                    self.write_byte(pos)?;
+                    pos += 1;
+                }
+                _ => pos = self.write_path(pos, depth, nesting)?,
+            }
+        }
+
+        Ok(pos)
+    }
+
+    /// Writes the path beginning at `pos`, along with its type parameters or
+    /// function parameters, and returns the position after them.
+    fn write_path(&mut self, pos: usize, depth: usize, nesting: usize) -> Result<usize, Error> {
+        let bytes = self.type_name_qualified.as_bytes();
+        if let Some((end, kind, item_path)) = self.closure_path(pos) {
+            write_closure_name(
+                self.buffer,
+                kind,
+                None::<fn(&mut W) -> Result<(), Error>>,
+                ItemPathSegments { item_path },
+                self.fmt,
+            )?;
+            return Ok(end);
+        }
+
+        let path_end = path_end(bytes, pos);
+        let path = &self.type_name_qualified[pos..path_end];
+        write_path(self.buffer, path, self.fmt)?;
+
+        match bytes.get(path_end) {
+            // Higher-ranked lifetimes are not type parameters.
+            Some(b'<') if path == "for" => self.write_group(path_end, depth, nesting, false),
+            Some(b'<') => match self.fmt.type_params_fmt_opts {
+                TypeParamsFmtOpts::None => Ok(group_end(bytes, path_end)),
+                TypeParamsFmtOpts::Std
+                    if !path
+                        .split_once("::")
+                        .is_some_and(|(crate_name, _)| self.fmt.is_std_crate(crate_name)) =>
+                {
+                    Ok(group_end(bytes, path_end))
+                }
+                _ => self.write_group(path_end, depth, nesting, true),
+            },
+            // Parameters of `fn` pointers and `Fn` traits.
+            Some(b'(') => self.write_group(path_end, depth, nesting, false),
+            _ => Ok(path_end),
+        }
+    }
+
+    /// Writes the qualified path that begins with the `<` at `open`, e.g.
+    /// `<alloc::vec::Vec<u8> as core::iter::IntoIterator>`, and returns the
+    /// position after it.
+    ///
+    /// Closures in trait impl methods are written in short form, e.g.
+    /// `{closure in <Vec<u8> as IntoIterator>::into_iter}`.
+    fn write_qualified_path(
+        &mut self,
+        open: usize,
+        depth: usize,
+        nesting: usize,
+    ) -> Result<usize, Error> {
+        let bytes = self.type_name_qualified.as_bytes();
+        let close = group_end(bytes, open);
+        let closure_path = match bytes.get(close..close + 3) {
+            Some([b':', b':', next]) if !is_delimiter(*next) => self.closure_path(close + 2),
+            _ => None,
+        };
+        let Some((end, kind, item_path)) = closure_path else {
+            return self.write_group(open, depth, nesting, false);
+        };
+
+        let type_name_qualified = self.type_name_qualified;
+        let fmt = self.fmt;
+        write_closure_name(
+            self.buffer,
+            kind,
+            Some(|buffer: &mut W| {
+                Stream {
+                    buffer,
+                    type_name_qualified,
+                    fmt,
+                }
+                .write_group(open, depth, nesting, false)
+                .map(|_| ())
+            }),
+            ItemPathSegments { item_path },
+            fmt,
+        )?;
+
+        Ok(end)
+    }
+
+    /// Returns the end of the closure path that begins at `start`, the kind of
+    /// closure, and the path of the item that the closure is defined in, if
+    /// the path names a closure.
+    ///
+    /// Closures in generic functions and in methods of generic types continue
+    /// the path after the generic arguments, e.g.
+    /// `my_crate::Foo<u8>::run::{{closure}}`.
+    fn closure_path(&self, start: usize) -> Option<(usize, ClosureKind, &'s str)> {
+        let type_name_qualified = self.type_name_qualified;
+        let bytes = type_name_qualified.as_bytes();
+        let mut pos = start;
+        loop {
+            let end = path_end(bytes, pos);
+            let path = &type_name_qualified[pos..end];
+            let segment_start = path.rfind("::").map_or(pos, |index| pos + index + 2);
+            let is_type_params = bytes.get(end) == Some(&b'<');
+
+            if let Some(kind) = closure_kind(&type_name_qualified[segment_start..end]) {
+                // Closure segments do not have generic arguments, so any that
+                // follow the first path are discarded.
+                if pos == start || !is_type_params {
+                    let end = if is_type_params {
+                        group_end(bytes, end)
+                    } else {
+                        end
+                    };
+                    let item_path = &type_name_qualified[start..segment_start];
+                    let item_path = item_path.strip_suffix("::").unwrap_or(item_path);
+
+                    return Some((end, kind, item_path));
+                }
+            }
+
+            // Higher-ranked lifetimes are not generic arguments.
+            if !is_type_params || path == "for" {
+                return None;
+            }
+            let close = group_end(bytes, end);
+            match bytes.get(close..close + 3) {
+                Some([b':', b':', next]) if !is_delimiter(*next) => pos = close + 2,
+                _ => return None,
+            }
+        }
+    }
+
+    /// Writes the bracketed group that opens at `open`, and returns the
+    /// position after it.
+    ///
+    /// # Parameters
+    ///
+    /// * `open`: Position of the opening bracket.
+    /// * `depth`: Number of type parameter levels that the group is nested in.
+    /// * `nesting`: Number of brackets that the group is nested in.
+    /// * `is_type_params`: Whether the group holds type parameters or tuple
+    ///   fields, which are elided according to the type parameter formatting
+    ///   options.
+    fn write_group(
+        &mut self,
+        open: usize,
+        depth: usize,
+        nesting: usize,
+        is_type_params: bool,
+    ) -> Result<usize, Error> {
+        let bytes = self.type_name_qualified.as_bytes();
+
+        if nesting >= MAX_NESTING {
+            let end = group_end(bytes, open);
+            return self
+                .buffer
+                .write_str(&self.type_name_qualified[open..end])
+                .map(|_| end);
+        }
+
+        self.write_byte(open)?;
+
+        let close = if !is_type_params {
+            self.write_level(open + 1, depth, nesting + 1, false)?
+        } else {
+            let (shown_count, marker) = self
+                .fmt
+                .type_params_fmt_opts
+                .type_params_shown(depth, params_count(bytes, open));
+
+            match marker {
+                Some(marker) => {
+                    let mut pos = open + 1;
+                    (0..shown_count).try_for_each(|_| {
+                        // Skip the `, ` after each parameter, which is
+                        // written below.
+                        pos = self.write_level(pos, depth + 1, nesting + 1, true)? + 1;
+                        while bytes.get(pos) == Some(&b' ') {
+                            pos += 1;
+                        }
+
+                        self.buffer.write_str(", ")
+                    })?;
+                    self.buffer.write_str(marker)?;
+
+                    group_close(bytes, open)
+                }
+                None => self.write_level(open + 1, depth + 1, nesting + 1, false)?,
+            }
+        };
+
+        if close < bytes.len() {
+            self.write_byte(close)?;
+            Ok(close + 1)
+        } else {
+            Ok(close)
+        }
+    }
+
+    /// Returns whether the path beginning at `pos` is an auto trait that is
+    /// elided from trait objects.
+    fn is_elided_auto_trait(&self, pos: usize) -> bool {
+        let bytes = self.type_name_qualified.as_bytes();
+        let path = &self.type_name_qualified[pos..path_end(bytes, pos)];
+        let (crate_name, simple_name) = match (path.split_once("::"), path.rsplit_once("::")) {
+            (Some((crate_name, _)), Some((_, simple_name))) => (Some(crate_name), simple_name),
+            _ => (None, path),
+        };
+
+        // Auto traits do not have type parameters.
+        bytes.get(pos + path.len()) != Some(&b'<')
+            && self.fmt.is_elided_auto_trait(crate_name, simple_name)
+    }
+
+    /// Writes the ASCII byte at `pos`.
+    fn write_byte(&mut self, pos: usize) -> Result<(), Error> {
+        self.buffer
+            .write_str(&self.type_name_qualified[pos..pos + 1])
+    }
+}
+
+/// Returns whether the byte separates paths, as opposed to being part of a
+/// path segment.
+///
+/// Non-ASCII bytes are always part of a path segment, so the returned
+/// positions are always on `char` boundaries.
+fn is_delimiter(byte: u8) -> bool {
+    b"<>()[],; &*:=+".contains(&byte)
+}
+
+/// Returns the end of the path that begins at `start`.
+///
+/// Segments are separated by `::`, so a `::` that is not followed by another
+/// segment, such as the one in `>::IntoIter`, ends the path.
+fn path_end(bytes: &[u8], start: usize) -> usize {
+    let mut pos = start;
+    loop {
+        while pos < bytes.len() && !is_delimiter(bytes[pos]) {
+            pos += 1;
+        }
+
+        match bytes.get(pos..pos + 3) {
+            Some([b':', b':', next]) if !is_delimiter(*next) => pos += 2,
+            _ => return pos,
+        }
+    }
+}
+
+/// Returns the position of the bracket that closes the group opened at
+/// `open`, or the end of the string if the group is not closed.
+///
+/// The `>` in `->` does not close a group.
+fn group_close(bytes: &[u8], open: usize) -> usize {
+    let mut depth = 0usize;
+    let mut pos = open;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'<' | b'(' | b'[' => depth += 1,
+            b'>' if pos > 0 && bytes[pos - 1] == b'-' => {}
+            b'>' | b')' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return pos;
+                }
+            }
+            _ => {}
+        }
+        pos += 1;
+    }
+
+    bytes.len()
+}
+
+/// Returns the position after the group opened at `open`.
+fn group_end(bytes: &[u8], open: usize) -> usize {
+    (group_close(bytes, open) + 1).min(bytes.len())
+}
+
+/// Returns the number of comma separated parameters in the group opened at
+/// `open`.
+///
+/// A trailing comma, such as the one in `(u8,)`, does not begin a parameter.
+fn params_count(bytes: &[u8], open: usize) -> usize {
+    let inner = &bytes[open + 1..group_close(bytes, open)];
+
+    let mut depth = 0usize;
+    let mut count = 0;
+    let mut param_is_empty = true;
+    inner
+        .iter()
+        .enumerate()
+        .for_each(|(index, byte)| match byte {
+            b'<' | b'(' | b'[' => {
+                depth += 1;
+                param_is_empty = false;
+            }
+            b'>' if index > 0 && inner[index - 1] == b'-' => {}
+            b'>' | b')' | b']' => depth = depth.saturating_sub(1),
+            b',' if depth == 0 => {
+                count += usize::from(!param_is_empty);
+                param_is_empty = true;
+            }
+            b' ' => {}
+            _ => param_is_empty = false,
+        });
+
+    count + usize::from(!param_is_empty)
+}
+
+/// Writes the path with the module segments that the formatting options
+/// include, mapping the crate name if standard library paths are normalized.
+///
+/// This matches the output of `TypeNameStruct::write_module_path` followed by
+/// the simple name.
+fn write_path<W>(buffer: &mut W, path: &str, fmt: &TypeNameFmt) -> Result<(), Error>
+where
+    W: Write,
+{
+    let Some((module_path, simple_name)) = path.rsplit_once("::") else {
+        return buffer.write_str(path);
+    };
+
+    match fmt.public_module_path(module_path.split("::"), simple_name) {
+        Some(public_module_path) => write_module_path(
+            buffer,
+            public_module_path.iter().copied(),
+            public_module_path.len(),
+            fmt,
+        )?,
+        None => write_module_path(
+            buffer,
+            module_path.split("::"),
+            module_path.split("::").count(),
+            fmt,
+        )?,
+    }
+
+    buffer.write_str(simple_name)
+}
+
+/// Writes the module segments that the formatting options include, each
+/// followed by `::`.
+///
+/// # Parameters
+///
+/// * `buffer`: Buffer to write to.
+/// * `module_path`: Module path segments.
+/// * `module_segment_count`: Number of module path segments.
+/// * `fmt`: Format options, which specify the number of module segments to
+///   include, and whether to normalize the crate name.
+pub(crate) fn write_module_path<'p, W>(
+    buffer: &mut W,
+    module_path: impl Iterator<Item = &'p str>,
+    module_segment_count: usize,
+    fmt: &TypeNameFmt,
+) -> Result<(), Error>
+where
+    W: Write,
+{
+    let m = fmt.segment_count_left;
+    let n = fmt.segment_count_right;
+    let is_shortened = m.saturating_add(n) < module_segment_count;
+
+    module_path.enumerate().try_for_each(|(index, segment)| {
+        // If we skipped any module segments, indicate this with `".."`
+        if is_shortened && index == m && m.saturating_add(n) > 0 {
+            buffer.write_str("..::")?;
+        }
+
+        if !is_shortened || index < m || index >= module_segment_count - n {
+            let segment = if index == 0 {
+                fmt.crate_name(segment)
+            } else {
+                segment
+            };
+            buffer.write_str(segment)?;
+            buffer.write_str("::")?;
+        }
+
+        Ok(())
+    })
+}
+
+/// Iterator over the segments of the path of the item that a closure is
+/// defined in, skipping the generic arguments of generic functions and types.
+#[derive(Clone)]
+struct ItemPathSegments<'s> {
+    /// Remaining item path, e.g. `my_crate::Foo<u8>::run`.
+    item_path: &'s str,
+}
+
+impl<'s> Iterator for ItemPathSegments<'s> {
+    type Item = &'s str;
+
+    fn next(&mut self) -> Option<&'s str> {
+        if self.item_path.is_empty() {
+            return None;
+        }
+
+        let bytes = self.item_path.as_bytes();
+        let segment_end = bytes
+            .iter()
+            .position(|byte| is_delimiter(*byte))
+            .unwrap_or(bytes.len());
+        let segment = &self.item_path[..segment_end];
+        let rest = match bytes.get(segment_end) {
+            Some(b'<') => &self.item_path[group_end(bytes, segment_end)..],
+            _ => &self.item_path[segment_end..],
+        };
+        self.item_path = rest.strip_prefix("::").unwrap_or("");
+
+        Some(segment)
+    }
+}
+
+/// Writes the short form of a closure's name, e.g. `{closure in main}`.
+///
+/// # Parameters
+///
+/// * `buffer`: Buffer to write to.
+/// * `kind`: Kind of closure.
+/// * `write_qualified_path`: Writes the qualified path that the item path
+///   continues, e.g. `<my_crate::Foo as my_crate::Tr>`, if any.
+/// * `item_path`: Segments of the path of the item that the closure is defined
+///   in, without generic arguments.
+/// * `fmt`: Format options for the module path.
+pub(crate) fn write_closure_name<'p, W, Q>(
+    buffer: &mut W,
+    kind: ClosureKind,
+    write_qualified_path: Option<Q>,
+    item_path: impl Iterator<Item = &'p str> + Clone,
+    fmt: &TypeNameFmt,
+) -> Result<(), Error>
+where
+    W: Write,
+    Q: FnOnce(&mut W) -> Result<(), Error>,
+{
+    buffer.write_str("{")?;
+    buffer.write_str(kind.as_str())?;
+
+    // Enclosing closure segments are skipped, so that nested closures are
+    // named after the function they are defined in.
+    let fn_name_index = item_path
+        .clone()
+        .enumerate()
+        .filter(|(_, segment)| !segment.starts_with('{'))
+        .last()
+        .map(|(index, _)| index);
+    if fn_name_index.is_some() || write_qualified_path.is_some() {
+        if kind != ClosureKind::AsyncFnBody {
+            buffer.write_str(" in")?;
+        }
+        buffer.write_str(" ")?;
+    }
+
+    match (write_qualified_path, fn_name_index) {
+        (Some(write_qualified_path), fn_name_index) => {
+            write_qualified_path(buffer)?;
+            let item_segment_count = fn_name_index.map_or(0, |index| index + 1);
+            item_path.take(item_segment_count).try_for_each(|segment| {
+                buffer.write_str("::")?;
+                buffer.write_str(segment)
+            })?;
+        }
+        (None, Some(fn_name_index)) => {
+            let mut item_path = item_path;
+            write_module_path(
+                buffer,
+                item_path.by_ref().take(fn_name_index),
+                fn_name_index,
+                fmt,
+            )?;
+            if let Some(fn_name) = item_path.next() {
+                buffer.write_str(fn_name)?;
+            }
+        }
+        (None, None) => {}
+    }
+
+    buffer.write_str("}")
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::string::String;
+
+    use super::write_type_name_str_with;
+    use crate::{TypeName, TypeNameFmt, TypeParamsFmtOpts};
+
+    fn stream(type_name_qualified: &str, fmt: &TypeNameFmt) -> String {
+        let mut buffer = String::new();
+        write_type_name_str_with(&mut buffer, type_name_qualified, fmt)
+            .expect("Writing to a `String` never fails.");
+
+        buffer
+    }
+
+    #[test]
+    fn stream_matches_parsed_type_name() {
+        [
+            "usize",
+            "core::option::Option<alloc::string::String>",
+            "core::result::Result<alloc::vec::Vec<(u8, &'_ str)>, alloc::boxed::Box<dyn \
+            core::error::Error + core::marker::Send>>",
+            "my_crate::a::b::Wrapper<my_crate::Inner<u8>, [alloc::string::String; 3]>",
+            "alloc::boxed::Box<dyn core::ops::function::Fn(&'_ str) -> [u8; 3] + core::marker::Send>",
+            "<alloc::vec::Vec<u8> as core::iter::traits::collect::IntoIterator>::IntoIter",
+            "fn(my_crate::Key<u8>) -> core::option::Option<my_crate::Value<u8>>",
+            "*const [my_crate::a::Node<my_crate::b::Leaf>]",
+            "dyn core::iter::traits::iterator::Iterator<Item = my_crate::Item<u8>>",
+            "core::result::Result<(u8, (alloc::string::String,), ()), my_crate::Error<u8, u16, u32>>",
+            "&(dyn core::any::Any + core::marker::Send)",
+            "alloc::boxed::Box<dyn 'static>",
+            "for<'a> fn(&'a my_crate::Key<u8, u16>, (u8, u16, u32)) -> my_crate::Value<u8>",
+            "my_crate::a::main::{{closure}}",
+            "my_crate::a::main::{closure#0}::{closure#1}",
+            "core::option::Option<my_crate::a::run<u8>::{{closure}}>",
+            "my_crate::a::Foo<alloc::string::String>::run<u8>::{{closure}}",
+            "<my_crate::a::Foo<u8> as my_crate::b::Tr>::run::{{closure}}::{{closure}}",
+            "<my_crate::a::Foo<u8> as my_crate::b::Tr>::run<u16>::{{closure}}",
+            "{{closure}}",
+            "my_crate::a::Foo<r#type = my_crate::b::Bar<u8>>",
+        ]
+        .iter()
+        .for_each(|type_name_qualified| {
+            let type_name = TypeName::from(*type_name_qualified);
+
+            [(0, 0), (1, 0), (0, 1), (1, 1), (2, 2), (usize::MAX, 0)]
+                .iter()
+                .for_each(|&(m, n)| {
+                    [
+                        TypeParamsFmtOpts::All,
+                        TypeParamsFmtOpts::Std,
+                        TypeParamsFmtOpts::None,
+                        TypeParamsFmtOpts::MaxDepth {
+                            max_depth: 0,
+                            marker: "..",
+                        },
+                        TypeParamsFmtOpts::MaxDepth {
+                            max_depth: 1,
+                            marker: "~",
+                        },
+                        TypeParamsFmtOpts::MaxParams {
+                            max_params: 0,
+                            marker: "..",
+                        },
+                        TypeParamsFmtOpts::MaxParams {
+                            max_params: 1,
+                            marker: "\u{2026}",
+                        },
+                    ]
+                    .iter()
+                    .for_each(|&type_params_fmt_opts| {
+                        let fmt = TypeNameFmt::mn_opts(m, n, type_params_fmt_opts);
+                        [
+                            fmt,
+                            fmt.normalize_std_paths(true),
+                            fmt.elide_auto_traits(true),
+                            fmt.public_std_paths(true),
+                        ]
+                        .iter()
+                        .for_each(|fmt| {
+                            assert_eq!(
+                                type_name.as_str_with(fmt),
+                                stream(type_name_qualified, fmt),
+                                "`{type_name_qualified}` with `{fmt:?}`",
+                            );
+                        });
+                    });
+                });
+        });
+    }
+
+    #[test]
+    fn stream_writes_closures_in_short_form() {
+        assert_eq!(
+            "{closure in main}",
+            stream("my_crate::main::{{closure}}", &TypeNameFmt::new())
+        );
+        assert_eq!(
+            "{closure in ..::a::main}",
+            stream(
+                "my_crate::a::main::{{closure}}",
+                &TypeNameFmt::new().segment_count_right(1)
+            )
+        );
+    }
+}
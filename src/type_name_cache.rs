@@ -0,0 +1,305 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::any::TypeId;
+
+use crate::{TypeNameFmt, TypeParamsFmtOpts};
+
+/// Type names of one type, each with the options it was formatted with.
+///
+/// A program formats each type with few different options, so these are
+/// searched linearly.
+type Formatted<S> = Vec<(TypeNameFmt, S)>;
+
+/// Caches shortened type names, so that each is only computed once.
+///
+/// Type names are looked up by the type's `TypeId`, so the type name string is
+/// not hashed or compared on each lookup.
+///
+/// This is usable in `no_std` builds. With the `std` feature, the
+/// `tynm::type_name_static` function uses a global cache instead.
+///
+/// # Example
+///
+/// ```rust
+/// use tynm::{TypeNameCache, TypeNameFmt};
+///
+/// let mut cache = TypeNameCache::new();
+///
+/// assert_eq!(cache.type_name::<Option<String>>(), "Option<String>");
+/// assert_eq!(cache.len(), 1);
+///
+/// // Computed type names are reused.
+/// assert_eq!(cache.type_name::<Option<String>>(), "Option<String>");
+/// assert_eq!(cache.len(), 1);
+///
+/// let fmt = TypeNameFmt::new()
+///     .normalize_std_paths(true)
+///     .segment_count_left(1);
+/// assert_eq!(
+///     cache.type_name_with::<Option<String>>(&fmt),
+///     "std::..::Option<std::..::String>"
+/// );
+/// assert_eq!(cache.len(), 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeNameCache {
+    /// Shortened type names of each type.
+    type_names: BTreeMap<TypeId, Formatted<String>>,
+}
+
+impl TypeNameCache {
+    /// Returns a new, empty `TypeNameCache`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the simple type name, computing it if it is not cached.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T`: Type whose simple type name should be returned.
+    pub fn type_name<T>(&mut self) -> &str
+    where
+        T: ?Sized + 'static,
+    {
+        self.type_namemn_opts::<T>(0, 0, TypeParamsFmtOpts::All)
+    }
+
+    /// Returns the type name with `m` most significant, and `n` least
+    /// significant module path segments, computing it if it is not cached.
+    ///
+    /// # Parameters
+    ///
+    /// * `m`: Number of most significant module path segments to include.
+    /// * `n`: Number of least significant module path segments to include.
+    /// * `type_params_fmt_opts`: How to format type parameters, see the type
+    ///   documentation for details.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T`: Type whose simple type name should be returned.
+    pub fn type_namemn_opts<T>(
+        &mut self,
+        m: usize,
+        n: usize,
+        type_params_fmt_opts: TypeParamsFmtOpts,
+    ) -> &str
+    where
+        T: ?Sized + 'static,
+    {
+        self.type_name_with::<T>(&TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
+
+    /// Returns the type name formatted with the given options, computing it if
+    /// it is not cached.
+    ///
+    /// # Parameters
+    ///
+    /// * `fmt`: Options for formatting the type name.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T`: Type whose type name should be returned.
+    pub fn type_name_with<T>(&mut self, fmt: &TypeNameFmt) -> &str
+    where
+        T: ?Sized + 'static,
+    {
+        let formatted = self.type_names.entry(TypeId::of::<T>()).or_default();
+        let index = match position(formatted, fmt) {
+            Some(index) => index,
+            None => {
+                let type_name = crate::type_name_with::<T>(fmt);
+                formatted.push((*fmt, type_name));
+                formatted.len() - 1
+            }
+        };
+
+        &formatted[index].1
+    }
+
+    /// Returns the number of cached type names.
+    pub fn len(&self) -> usize {
+        self.type_names.values().map(Vec::len).sum()
+    }
+
+    /// Returns whether no type names are cached.
+    pub fn is_empty(&self) -> bool {
+        self.type_names.is_empty()
+    }
+
+    /// Removes all cached type names.
+    pub fn clear(&mut self) {
+        self.type_names.clear();
+    }
+}
+
+/// Returns the index of the type name formatted with the given options.
+fn position<S>(formatted: &[(TypeNameFmt, S)], fmt: &TypeNameFmt) -> Option<usize> {
+    formatted
+        .iter()
+        .position(|(formatted_fmt, _)| formatted_fmt == fmt)
+}
+
+/// Returns the formatted type name from the global cache, computing and
+/// leaking it if it is not cached.
+///
+/// Each combination of type and options is leaked at most once, so memory use
+/// is bounded by the number of distinct combinations used by the program.
+/// Functions set with `TypeNameFmt::std_crates_fn` are compared by address,
+/// which is not unique to the function, so each distinct function pointer
+/// value counts as a distinct combination.
+///
+/// Each thread also keeps the type names that it has read, so repeated reads
+/// do not take the global cache's lock.
+#[cfg(feature = "std")]
+pub(crate) fn type_name_with_static<T>(fmt: &TypeNameFmt) -> &'static str
+where
+    T: ?Sized + 'static,
+{
+    use std::{
+        boxed::Box,
+        cell::RefCell,
+        collections::HashMap,
+        sync::{OnceLock, PoisonError, RwLock},
+    };
+
+    type StaticTypeNames = HashMap<TypeId, Formatted<&'static str>>;
+
+    static TYPE_NAMES: OnceLock<RwLock<StaticTypeNames>> = OnceLock::new();
+    std::thread_local! {
+        static THREAD_TYPE_NAMES: RefCell<StaticTypeNames> = RefCell::default();
+    }
+
+    let find = |type_names: &StaticTypeNames, type_id: &TypeId| {
+        type_names
+            .get(type_id)
+            .and_then(|formatted| position(formatted, fmt).map(|index| formatted[index].1))
+    };
+    let type_id = TypeId::of::<T>();
+
+    let global_type_name = || {
+        let type_names = TYPE_NAMES.get_or_init(RwLock::default);
+        let type_name = find(
+            &type_names.read().unwrap_or_else(PoisonError::into_inner),
+            &type_id,
+        );
+        if let Some(type_name) = type_name {
+            return type_name;
+        }
+
+        // Another thread may have inserted the type name between releasing the
+        // read lock and acquiring the write lock, so it is looked up again to
+        // avoid leaking it twice.
+        let mut type_names = type_names.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(type_name) = find(&type_names, &type_id) {
+            return type_name;
+        }
+
+        let type_name: &'static str = Box::leak(crate::type_name_with::<T>(fmt).into_boxed_str());
+        type_names
+            .entry(type_id)
+            .or_default()
+            .push((*fmt, type_name));
+
+        type_name
+    };
+
+    // The thread's cache is unavailable while thread locals are destroyed.
+    THREAD_TYPE_NAMES
+        .try_with(|thread_type_names| {
+            if let Some(type_name) = find(&thread_type_names.borrow(), &type_id) {
+                return type_name;
+            }
+
+            let type_name = global_type_name();
+            thread_type_names
+                .borrow_mut()
+                .entry(type_id)
+                .or_default()
+                .push((*fmt, type_name));
+
+            type_name
+        })
+        .unwrap_or_else(|_| global_type_name())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::TypeNameCache;
+    use crate::{TypeNameFmt, TypeParamsFmtOpts};
+
+    #[test]
+    fn type_name_cache_computes_each_combination_once() {
+        let mut cache = TypeNameCache::new();
+
+        assert_eq!("Option<String>", cache.type_name::<Option<String>>());
+        assert_eq!(
+            "core::..::Option<alloc::..::String>",
+            cache.type_namemn_opts::<Option<String>>(1, 0, TypeParamsFmtOpts::All)
+        );
+        assert_eq!("Option<String>", cache.type_name::<Option<String>>());
+        assert_eq!(
+            "Option<String>",
+            cache.type_name_with::<Option<String>>(&TypeNameFmt::new())
+        );
+        assert_eq!("u8", cache.type_name::<u8>());
+        assert_eq!(3, cache.len());
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn type_name_cache_distinguishes_fmt_options() {
+        let mut cache = TypeNameCache::new();
+        let fmt = TypeNameFmt::new().segment_count_left(1);
+
+        assert_eq!(
+            "core::..::Option<alloc::..::String>",
+            cache.type_name_with::<Option<String>>(&fmt)
+        );
+        assert_eq!(
+            "std::..::Option<std::..::String>",
+            cache.type_name_with::<Option<String>>(&fmt.normalize_std_paths(true))
+        );
+        assert_eq!(2, cache.len());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn type_name_static_returns_same_str_for_same_options() {
+        let first = crate::type_name_static::<Option<String>>();
+        let second = crate::type_name_static::<Option<String>>();
+
+        assert_eq!("Option<String>", first);
+        assert!(core::ptr::eq(first, second));
+        assert_eq!(
+            "..::option::Option<..::string::String>",
+            crate::type_namemn_opts_static::<Option<String>>(0, 1, TypeParamsFmtOpts::All)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn type_name_with_static_shares_type_names_between_threads() {
+        use alloc::vec::Vec;
+
+        let fmt = TypeNameFmt::new()
+            .public_std_paths(true)
+            .segment_count_left(1);
+
+        let first = crate::type_name_with_static::<Option<Vec<u8>>>(&fmt);
+        let second =
+            std::thread::spawn(move || crate::type_name_with_static::<Option<Vec<u8>>>(&fmt))
+                .join()
+                .expect("Expected thread to return the type name.");
+
+        assert_eq!("std::..::Option<std::..::Vec<u8>>", first);
+        assert!(core::ptr::eq(first, second));
+        assert_eq!(
+            "Option<Vec<u8>>",
+            crate::type_name_with_static::<Option<Vec<u8>>>(&TypeNameFmt::new())
+        );
+    }
+}
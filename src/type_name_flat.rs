@@ -0,0 +1,1415 @@
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+use core::{
+    fmt::{Error, Write},
+    ops::Range,
+};
+
+use crate::{
+    parser::{self, Builder, Nesting},
+    render::{self, NodeView, RenderContext, RenderNode},
+    types::{
+        TypeNameArray, TypeNameBinding, TypeNameClosure, TypeNameFunction, TypeNamePointer,
+        TypeNameQualifiedPath, TypeNameReference, TypeNameSlice, TypeNameStruct, TypeNameTrait,
+        TypeNameTraitBound, TypeNameTuple,
+    },
+    ClosureKind, GenericArg, ParseLimits, TypeName, TypeNameFmt, TypeParamsFmtOpts,
+};
+
+/// Index of a node in a `TypeNameFlat`.
+type NodeId = u32;
+
+/// A type name stored as a flat list of nodes, which reference their children
+/// by index.
+///
+/// A `TypeName` tree allocates a `Box` or `Vec` for most of its nodes. A
+/// `TypeNameFlat` is built by the parser directly into three `Vec`s of nodes,
+/// path segments, and child indices, and its text is kept in a single string
+/// that nodes reference by byte offsets. This makes it cheaper to keep many
+/// type names in memory, and [`TypeNameFlat::into_owned`] copies the text in
+/// one allocation.
+///
+/// Nodes are read through [`TypeNameFlatNode`]s, beginning from
+/// [`TypeNameFlat::root`].
+///
+/// # Example
+///
+/// ```rust
+/// use tynm::{TypeNameFlat, TypeNameFlatKind};
+///
+/// let flat =
+///     TypeNameFlat::from("std::collections::hash::map::HashMap<alloc::string::String, u8>");
+///
+/// let root = flat.root();
+/// assert_eq!(root.kind(), TypeNameFlatKind::Struct);
+/// assert!(root.module_path().eq(["std", "collections", "hash", "map"]));
+/// assert_eq!(root.simple_name(), "HashMap");
+///
+/// let type_params = root
+///     .type_params()
+///     .map(|type_param| type_param.simple_name())
+///     .collect::<Vec<_>>();
+/// assert_eq!(type_params, ["String", "u8"]);
+///
+/// assert_eq!(flat.as_str(), "HashMap<String, u8>");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeNameFlat<'s> {
+    /// Type name string that the text of nodes and segments is sliced from.
+    text: Cow<'s, str>,
+    /// Nodes of the type name, with each node after its children.
+    nodes: Vec<Node>,
+    /// Path segments and lifetimes referenced by nodes.
+    segments: Vec<Span>,
+    /// Child node indices referenced by nodes.
+    children: Vec<NodeId>,
+    /// Index of the outermost node.
+    root: NodeId,
+}
+
+impl<'s> TypeNameFlat<'s> {
+    /// Returns the outermost type.
+    pub fn root(&self) -> TypeNameFlatNode<'_, 's> {
+        TypeNameFlatNode {
+            flat: self,
+            id: self.root,
+        }
+    }
+
+    /// Returns the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the type name as a `TypeName` tree.
+    pub fn to_type_name(&self) -> TypeName<'_> {
+        self.root().to_type_name()
+    }
+
+    /// Returns the type name string without any module paths.
+    ///
+    /// This is equivalent to calling `TypeNameFlat::as_str_mn_opts(0, 0,
+    /// TypeParamsFmtOpts::All);`
+    pub fn as_str(&self) -> String {
+        self.as_str_mn_opts(0, 0, TypeParamsFmtOpts::All)
+    }
+
+    /// Returns the type name string with the given number of module segments.
+    ///
+    /// See [`TypeName::as_str_mn_opts`] for details.
+    ///
+    /// # Parameters
+    ///
+    /// * `m`: Number of module segments to include, beginning from the left
+    ///   (most significant).
+    /// * `n`: Number of module segments to include, beginning from the right
+    ///   (least significant).
+    /// * `type_params_fmt_opts`: How to format type parameters, see the type
+    ///   documentation for details.
+    pub fn as_str_mn_opts(
+        &self,
+        m: usize,
+        n: usize,
+        type_params_fmt_opts: TypeParamsFmtOpts,
+    ) -> String {
+        self.as_str_with(&TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
+
+    /// Returns the type name string formatted with the given options.
+    pub fn as_str_with(&self, fmt: &TypeNameFmt) -> String {
+        let mut buffer = String::with_capacity(self.text.len());
+        self.write_str_with(&mut buffer, fmt)
+            .expect("Writing to a `String` never fails.");
+
+        buffer
+    }
+
+    /// Writes the type name string to the given buffer using the given format
+    /// options.
+    pub fn write_str_with<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.root().write_str_with(buffer, fmt)
+    }
+
+    /// Returns this type name with its text owned, so that it does not borrow
+    /// the string it was parsed from.
+    pub fn into_owned(self) -> TypeNameFlat<'static> {
+        TypeNameFlat {
+            text: Cow::Owned(self.text.into_owned()),
+            nodes: self.nodes,
+            segments: self.segments,
+            children: self.children,
+            root: self.root,
+        }
+    }
+}
+
+/// Parses a type name string.
+///
+/// If the string cannot be fully parsed, it is captured whole as a single
+/// [`TypeNameFlatKind::Unknown`] node.
+impl<'s> From<&'s str> for TypeNameFlat<'s> {
+    fn from(std_type_name: &'s str) -> Self {
+        let mut builder = FlatBuilder::new(std_type_name);
+        let nesting = Nesting::new(std_type_name, ParseLimits::default());
+        let root = match parser::type_name(&mut builder, std_type_name, nesting) {
+            Ok(("", root)) => root,
+            _ => {
+                builder.rollback(Checkpoint::default());
+                builder.unknown(std_type_name)
+            }
+        };
+
+        builder.finish(root)
+    }
+}
+
+impl TypeNameFlat<'static> {
+    /// Constructs a new `TypeNameFlat` with the name of `T`.
+    ///
+    /// This is equivalent to calling
+    /// `TypeNameFlat::from(core::any::type_name::<T>())`.
+    pub fn new<T: ?Sized>() -> Self {
+        Self::from(core::any::type_name::<T>())
+    }
+}
+
+/// Kind of a node in a `TypeNameFlat`.
+///
+/// These correspond to the variants of [`TypeName`] and [`GenericArg`], with
+/// the addition of `TraitBound` for each bound of a trait object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeNameFlatKind {
+    None,
+    Array,
+    Closure,
+    Function,
+    Never,
+    Pointer,
+    QualifiedPath,
+    Reference,
+    Slice,
+    Struct,
+    Tuple,
+    Trait,
+    TraitBound,
+    Unit,
+    Unknown,
+    Elided,
+    /// Const generic argument, e.g. `16` in `ArrayVec<u8, 16>`.
+    Const,
+    /// Lifetime generic argument, e.g. `'_` in `Cow<'_, str>`.
+    Lifetime,
+    /// Associated type binding, e.g. `Item = u8` in `dyn Iterator<Item = u8>`.
+    Binding,
+}
+
+/// A node in a [`TypeNameFlat`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TypeNameFlatNode<'f, 's> {
+    /// Type name that the node belongs to.
+    flat: &'f TypeNameFlat<'s>,
+    /// Index of the node.
+    id: NodeId,
+}
+
+impl<'f, 's> TypeNameFlatNode<'f, 's> {
+    /// Returns the kind of this node.
+    pub fn kind(self) -> TypeNameFlatKind {
+        match self.node() {
+            Node::None => TypeNameFlatKind::None,
+            Node::Array { .. } => TypeNameFlatKind::Array,
+            Node::Closure { .. } => TypeNameFlatKind::Closure,
+            Node::Function { .. } => TypeNameFlatKind::Function,
+            Node::Never => TypeNameFlatKind::Never,
+            Node::Pointer { .. } => TypeNameFlatKind::Pointer,
+            Node::QualifiedPath { .. } => TypeNameFlatKind::QualifiedPath,
+            Node::Reference { .. } => TypeNameFlatKind::Reference,
+            Node::Slice { .. } => TypeNameFlatKind::Slice,
+            Node::Struct { .. } => TypeNameFlatKind::Struct,
+            Node::Tuple { .. } => TypeNameFlatKind::Tuple,
+            Node::Trait { .. } => TypeNameFlatKind::Trait,
+            Node::TraitBound { .. } => TypeNameFlatKind::TraitBound,
+            Node::Unit => TypeNameFlatKind::Unit,
+            Node::Unknown(_) => TypeNameFlatKind::Unknown,
+            Node::Elided(_) => TypeNameFlatKind::Elided,
+            Node::Const(_) => TypeNameFlatKind::Const,
+            Node::Lifetime(_) => TypeNameFlatKind::Lifetime,
+            Node::Binding { .. } => TypeNameFlatKind::Binding,
+        }
+    }
+
+    /// Returns the module path of a struct, closure, or trait.
+    ///
+    /// For trait objects, this is the module path of the principal trait. For
+    /// other kinds, this is empty.
+    pub fn module_path(self) -> TypeNameFlatSegments<'f> {
+        match self.node() {
+            Node::Struct { module_path, .. } | Node::Closure { module_path, .. } => {
+                self.segments(*module_path)
+            }
+            Node::Trait { .. } | Node::TraitBound { .. } => self.principal_struct().map_or_else(
+                || self.segments(Span::EMPTY),
+                |principal| principal.module_path(),
+            ),
+            _ => self.segments(Span::EMPTY),
+        }
+    }
+
+    /// Returns the simple name of a struct or trait.
+    ///
+    /// For trait objects, this is the simple name of the principal trait. For
+    /// other kinds, this is empty.
+    pub fn simple_name(self) -> &'f str {
+        match self.node() {
+            Node::Struct { simple_name, .. } => self.text(*simple_name),
+            Node::Trait { .. } | Node::TraitBound { .. } => self
+                .principal_struct()
+                .map_or("", |principal| principal.simple_name()),
+            _ => "",
+        }
+    }
+
+    /// Returns the type parameters of a struct, tuple, or trait.
+    ///
+    /// For trait objects, these are the type parameters of the principal
+    /// trait. For other kinds, this is empty.
+    pub fn type_params(self) -> TypeNameFlatNodes<'f, 's> {
+        match self.node() {
+            Node::Struct { type_params, .. } | Node::Tuple { type_params } => {
+                self.nodes(*type_params)
+            }
+            Node::Trait { .. } | Node::TraitBound { .. } => self.principal_struct().map_or_else(
+                || self.nodes(Span::EMPTY),
+                |principal| principal.type_params(),
+            ),
+            _ => self.nodes(Span::EMPTY),
+        }
+    }
+
+    /// Returns the element or referenced type of an array, slice, pointer, or
+    /// reference.
+    pub fn type_param(self) -> Option<TypeNameFlatNode<'f, 's>> {
+        match self.node() {
+            Node::Array { type_param, .. }
+            | Node::Pointer { type_param, .. }
+            | Node::Reference { type_param, .. }
+            | Node::Slice { type_param } => Some(self.child(*type_param)),
+            _ => None,
+        }
+    }
+
+    /// Returns this node as a `TypeName` tree, which borrows its text from the
+    /// `TypeNameFlat`.
+    ///
+    /// This conversion is lossy for nodes that are not types: const, lifetime,
+    /// and binding generic arguments are returned as [`TypeName::Unknown`]
+    /// with their rendered text, and trait bounds as a trait object with a
+    /// single bound. Use [`TypeNameFlatNode::to_generic_arg`] to convert
+    /// generic arguments without loss.
+    pub fn to_type_name(self) -> TypeName<'f> {
+        match self.node() {
+            Node::Const(_) | Node::Lifetime(_) | Node::Binding { .. } => {
+                let mut text = String::new();
+                render::write_node_with(
+                    self,
+                    &mut text,
+                    &TypeNameFmt::full(),
+                    RenderContext::VERBATIM,
+                )
+                .expect("Writing to a `String` never fails.");
+
+                TypeName::Unknown(Cow::Owned(text))
+            }
+            Node::TraitBound { .. } => TypeName::Trait(TypeNameTrait {
+                bounds: alloc::vec![self.to_trait_bound()],
+                lifetime_bounds: Vec::new(),
+                parenthesized: false,
+            }),
+            _ => self.to_type_name_inner(),
+        }
+    }
+
+    /// Returns this node as a `GenericArg`, which borrows its text from the
+    /// `TypeNameFlat`.
+    ///
+    /// Nodes of the [`Const`], [`Lifetime`], and [`Binding`] kinds are returned
+    /// as the matching `GenericArg` variant, and other nodes as
+    /// [`GenericArg::Type`].
+    ///
+    /// [`Const`]: TypeNameFlatKind::Const
+    /// [`Lifetime`]: TypeNameFlatKind::Lifetime
+    /// [`Binding`]: TypeNameFlatKind::Binding
+    pub fn to_generic_arg(self) -> GenericArg<'f> {
+        match self.node() {
+            Node::Const(value) => GenericArg::Const(self.text_cow(*value)),
+            Node::Lifetime(lifetime) => GenericArg::Lifetime(self.text_cow(*lifetime)),
+            Node::Binding { name, type_name } => GenericArg::Binding(TypeNameBinding {
+                name: self.text_cow(*name),
+                type_name: self.child(*type_name).to_type_name_inner(),
+            }),
+            _ => GenericArg::Type(self.to_type_name_inner()),
+        }
+    }
+
+    /// Writes this node's type name string to the given buffer using the given
+    /// format options.
+    ///
+    /// This is written from the nodes directly, without building a `TypeName`
+    /// tree.
+    pub fn write_str_with<W>(self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        render::write_node(self, buffer, fmt)
+    }
+
+    /// Returns the node at the given index.
+    fn child(self, id: NodeId) -> Self {
+        Self {
+            flat: self.flat,
+            id,
+        }
+    }
+
+    /// Returns the node data.
+    fn node(self) -> &'f Node {
+        &self.flat.nodes[self.id as usize]
+    }
+
+    /// Returns the text in the span.
+    fn text(self, span: Span) -> &'f str {
+        &self.flat.text[span.range()]
+    }
+
+    /// Returns the text in the span, borrowed from the `TypeNameFlat`.
+    fn text_cow(self, span: Span) -> Cow<'f, str> {
+        Cow::Borrowed(self.text(span))
+    }
+
+    /// Returns an iterator over the segments whose indices are in the span.
+    fn segments(self, span: Span) -> TypeNameFlatSegments<'f> {
+        TypeNameFlatSegments {
+            text: &self.flat.text,
+            spans: self.flat.segments[span.range()].iter(),
+        }
+    }
+
+    /// Returns the segments in the span, borrowed from the `TypeNameFlat`.
+    fn segments_vec(self, span: Span) -> Vec<Cow<'f, str>> {
+        self.segments(span).map(Cow::Borrowed).collect()
+    }
+
+    /// Returns the indices of the child nodes in the span.
+    fn child_ids(self, span: Span) -> &'f [NodeId] {
+        &self.flat.children[span.range()]
+    }
+
+    /// Returns an iterator over the nodes whose indices are in the span.
+    fn nodes(self, span: Span) -> TypeNameFlatNodes<'f, 's> {
+        TypeNameFlatNodes {
+            flat: self.flat,
+            ids: self.child_ids(span).iter(),
+        }
+    }
+
+    /// Returns the struct node of a trait bound, or of the principal trait of a
+    /// trait object.
+    fn principal_struct(self) -> Option<Self> {
+        match self.node() {
+            Node::Trait { bounds, .. } => self
+                .nodes(*bounds)
+                .next()
+                .and_then(|principal| principal.principal_struct()),
+            Node::TraitBound { inner, .. } => Some(self.child(*inner)),
+            _ => None,
+        }
+    }
+
+    /// Returns this node as a `TypeName`, assuming it is a type.
+    fn to_type_name_inner(self) -> TypeName<'f> {
+        let type_name_box = |id: NodeId| Box::new(self.child(id).to_type_name_inner());
+        let type_names = |span: Span| {
+            self.nodes(span)
+                .map(|node| node.to_type_name_inner())
+                .collect::<Vec<_>>()
+        };
+
+        match self.node() {
+            Node::None => TypeName::None,
+            Node::Array { type_param, len } => TypeName::Array(TypeNameArray {
+                type_param: type_name_box(*type_param),
+                len: self.text_cow(*len),
+            }),
+            Node::Closure {
+                kind,
+                parent_path,
+                module_path,
+                segment,
+                enclosing_path,
+            } => TypeName::Closure(TypeNameClosure {
+                kind: *kind,
+                parent_path: type_names(*parent_path),
+                module_path: self.segments_vec(*module_path),
+                segment: self.text_cow(*segment),
+                enclosing_path: self.segments_vec(*enclosing_path),
+            }),
+            Node::Function {
+                bound_lifetimes,
+                is_unsafe,
+                abi,
+                params,
+                variadic,
+                return_type,
+            } => TypeName::Function(TypeNameFunction {
+                bound_lifetimes: self.segments_vec(*bound_lifetimes),
+                is_unsafe: *is_unsafe,
+                abi: abi.map(|abi| self.text_cow(abi)),
+                params: type_names(*params),
+                variadic: *variadic,
+                return_type: return_type.map(type_name_box),
+            }),
+            Node::Never => TypeName::Never,
+            Node::Pointer {
+                const_or_mut,
+                type_param,
+            } => TypeName::Pointer(TypeNamePointer {
+                const_or_mut: self.text_cow(*const_or_mut),
+                type_param: type_name_box(*type_param),
+            }),
+            Node::QualifiedPath {
+                self_type,
+                trait_path,
+                assoc_path,
+            } => TypeName::QualifiedPath(TypeNameQualifiedPath {
+                self_type: type_name_box(*self_type),
+                trait_path: trait_path.map(|trait_path| self.child(trait_path).to_struct()),
+                assoc_path: self.segments_vec(*assoc_path),
+            }),
+            Node::Reference {
+                lifetime,
+                mutable,
+                type_param,
+            } => TypeName::Reference(TypeNameReference {
+                lifetime: lifetime.map(|lifetime| self.text_cow(lifetime)),
+                mutable: *mutable,
+                type_param: type_name_box(*type_param),
+            }),
+            Node::Slice { type_param } => TypeName::Slice(TypeNameSlice {
+                type_param: type_name_box(*type_param),
+            }),
+            Node::Struct { .. } => TypeName::Struct(self.to_struct()),
+            Node::Tuple { type_params } => TypeName::Tuple(TypeNameTuple {
+                type_params: type_names(*type_params),
+            }),
+            Node::Trait {
+                bounds,
+                lifetime_bounds,
+                parenthesized,
+            } => TypeName::Trait(TypeNameTrait {
+                bounds: self
+                    .nodes(*bounds)
+                    .map(|bound| bound.to_trait_bound())
+                    .collect(),
+                lifetime_bounds: self.segments_vec(*lifetime_bounds),
+                parenthesized: *parenthesized,
+            }),
+            Node::Unit => TypeName::Unit,
+            Node::Unknown(text) => TypeName::Unknown(self.text_cow(*text)),
+            Node::Elided(text) => TypeName::Elided(self.text_cow(*text)),
+            Node::TraitBound { .. } | Node::Const(_) | Node::Lifetime(_) | Node::Binding { .. } => {
+                self.to_type_name()
+            }
+        }
+    }
+
+    /// Returns this node as a `TypeNameStruct`, assuming it is a struct.
+    fn to_struct(self) -> TypeNameStruct<'f> {
+        match self.node() {
+            Node::Struct {
+                module_path,
+                simple_name,
+                type_params,
+            } => TypeNameStruct {
+                module_path: self.segments_vec(*module_path),
+                simple_name: self.text_cow(*simple_name),
+                type_params: self
+                    .nodes(*type_params)
+                    .map(|type_param| type_param.to_generic_arg())
+                    .collect(),
+            },
+            _ => unreachable!("Trait paths are always stored as struct nodes."),
+        }
+    }
+
+    /// Returns this node as a `TypeNameTraitBound`, assuming it is a trait
+    /// bound.
+    fn to_trait_bound(self) -> TypeNameTraitBound<'f> {
+        match self.node() {
+            Node::TraitBound {
+                bound_lifetimes,
+                inner,
+                fn_params,
+                fn_return_type,
+            } => TypeNameTraitBound {
+                bound_lifetimes: self.segments_vec(*bound_lifetimes),
+                inner: self.child(*inner).to_struct(),
+                fn_params: fn_params.map(|fn_params| {
+                    self.nodes(fn_params)
+                        .map(|fn_param| fn_param.to_type_name_inner())
+                        .collect()
+                }),
+                fn_return_type: fn_return_type.map(|fn_return_type| {
+                    Box::new(self.child(fn_return_type).to_type_name_inner())
+                }),
+            },
+            _ => unreachable!("Trait object bounds are always stored as trait bound nodes."),
+        }
+    }
+}
+
+impl<'f, 's> RenderNode<'f> for TypeNameFlatNode<'f, 's> {
+    type Nodes = TypeNameFlatNodes<'f, 's>;
+    type Segments = TypeNameFlatSegments<'f>;
+
+    fn view(self) -> NodeView<'f, Self> {
+        match self.node() {
+            Node::None => NodeView::None,
+            Node::Array { type_param, len } => NodeView::Array {
+                type_param: self.child(*type_param),
+                len: self.text(*len),
+            },
+            Node::Closure {
+                kind,
+                parent_path,
+                module_path,
+                segment,
+                enclosing_path,
+            } => NodeView::Closure {
+                kind: *kind,
+                parent_path: self.nodes(*parent_path),
+                module_path: self.segments(*module_path),
+                segment: self.text(*segment),
+                enclosing_path: self.segments(*enclosing_path),
+            },
+            Node::Function {
+                bound_lifetimes,
+                is_unsafe,
+                abi,
+                params,
+                variadic,
+                return_type,
+            } => NodeView::Function {
+                bound_lifetimes: self.segments(*bound_lifetimes),
+                is_unsafe: *is_unsafe,
+                abi: abi.map(|abi| self.text(abi)),
+                params: self.nodes(*params),
+                variadic: *variadic,
+                return_type: return_type.map(|return_type| self.child(return_type)),
+            },
+            Node::Never => NodeView::Never,
+            Node::Pointer {
+                const_or_mut,
+                type_param,
+            } => NodeView::Pointer {
+                const_or_mut: self.text(*const_or_mut),
+                type_param: self.child(*type_param),
+            },
+            Node::QualifiedPath {
+                self_type,
+                trait_path,
+                assoc_path,
+            } => NodeView::QualifiedPath {
+                self_type: self.child(*self_type),
+                trait_path: trait_path.map(|trait_path| self.child(trait_path)),
+                assoc_path: self.segments(*assoc_path),
+            },
+            Node::Reference {
+                lifetime,
+                mutable,
+                type_param,
+            } => NodeView::Reference {
+                lifetime: lifetime.map(|lifetime| self.text(lifetime)),
+                mutable: *mutable,
+                type_param: self.child(*type_param),
+            },
+            Node::Slice { type_param } => NodeView::Slice {
+                type_param: self.child(*type_param),
+            },
+            Node::Struct {
+                module_path,
+                simple_name,
+                type_params,
+            } => NodeView::Struct {
+                module_path: self.segments(*module_path),
+                simple_name: self.text(*simple_name),
+                type_params: self.nodes(*type_params),
+            },
+            Node::Tuple { type_params } => NodeView::Tuple {
+                type_params: self.nodes(*type_params),
+            },
+            Node::Trait {
+                bounds,
+                lifetime_bounds,
+                parenthesized,
+            } => NodeView::Trait {
+                bounds: self.nodes(*bounds),
+                lifetime_bounds: self.segments(*lifetime_bounds),
+                parenthesized: *parenthesized,
+            },
+            Node::TraitBound {
+                bound_lifetimes,
+                inner,
+                fn_params,
+                fn_return_type,
+            } => NodeView::TraitBound {
+                bound_lifetimes: self.segments(*bound_lifetimes),
+                inner: self.child(*inner),
+                fn_params: fn_params.map(|fn_params| self.nodes(fn_params)),
+                fn_return_type: fn_return_type.map(|fn_return_type| self.child(fn_return_type)),
+            },
+            Node::Unit => NodeView::Unit,
+            Node::Unknown(text) => NodeView::Unknown(self.text(*text)),
+            Node::Elided(text) => NodeView::Elided(self.text(*text)),
+            Node::Const(value) => NodeView::Const(self.text(*value)),
+            Node::Lifetime(lifetime) => NodeView::Lifetime(self.text(*lifetime)),
+            Node::Binding { name, type_name } => NodeView::Binding {
+                name: self.text(*name),
+                type_name: self.child(*type_name),
+            },
+        }
+    }
+}
+
+/// Iterator over nodes in a [`TypeNameFlat`], such as the type parameters of a
+/// struct.
+#[derive(Clone, Debug)]
+pub struct TypeNameFlatNodes<'f, 's> {
+    /// Type name that the nodes belong to.
+    flat: &'f TypeNameFlat<'s>,
+    /// Indices of the remaining nodes.
+    ids: core::slice::Iter<'f, NodeId>,
+}
+
+impl<'f, 's> Iterator for TypeNameFlatNodes<'f, 's> {
+    type Item = TypeNameFlatNode<'f, 's>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ids.next().map(|id| TypeNameFlatNode {
+            flat: self.flat,
+            id: *id,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ids.size_hint()
+    }
+}
+
+impl ExactSizeIterator for TypeNameFlatNodes<'_, '_> {}
+
+/// Iterator over path segments in a [`TypeNameFlat`], such as the module path
+/// of a struct.
+#[derive(Clone, Debug)]
+pub struct TypeNameFlatSegments<'f> {
+    /// Text that the segments are sliced from.
+    text: &'f str,
+    /// Spans of the remaining segments.
+    spans: core::slice::Iter<'f, Span>,
+}
+
+impl<'f> Iterator for TypeNameFlatSegments<'f> {
+    type Item = &'f str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.spans.next().map(|span| &self.text[span.range()])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.size_hint()
+    }
+}
+
+impl ExactSizeIterator for TypeNameFlatSegments<'_> {}
+
+/// Range of byte offsets into the text, or of indices into the `segments` or
+/// `children`, of a `TypeNameFlat`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Span {
+    start: u32,
+    end: u32,
+}
+
+impl Span {
+    /// Span that contains no indices.
+    const EMPTY: Self = Self { start: 0, end: 0 };
+
+    /// Returns a new `Span` between the given indices.
+    fn new(start: usize, end: usize) -> Self {
+        Self {
+            start: to_index(start),
+            end: to_index(end),
+        }
+    }
+
+    /// Returns the indices in this span.
+    fn range(self) -> Range<usize> {
+        self.start as usize..self.end as usize
+    }
+}
+
+/// Node of a `TypeNameFlat`.
+///
+/// This mirrors `TypeName`, with boxed and `Vec` children replaced by indices
+/// into the `TypeNameFlat`, and strings replaced by spans of its text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Node {
+    None,
+    Array {
+        type_param: NodeId,
+        len: Span,
+    },
+    Closure {
+        kind: ClosureKind,
+        parent_path: Span,
+        module_path: Span,
+        segment: Span,
+        enclosing_path: Span,
+    },
+    Function {
+        bound_lifetimes: Span,
+        is_unsafe: bool,
+        abi: Option<Span>,
+        params: Span,
+        variadic: bool,
+        return_type: Option<NodeId>,
+    },
+    Never,
+    Pointer {
+        const_or_mut: Span,
+        type_param: NodeId,
+    },
+    QualifiedPath {
+        self_type: NodeId,
+        /// Index of a `Struct` node.
+        trait_path: Option<NodeId>,
+        assoc_path: Span,
+    },
+    Reference {
+        lifetime: Option<Span>,
+        mutable: bool,
+        type_param: NodeId,
+    },
+    Slice {
+        type_param: NodeId,
+    },
+    Struct {
+        module_path: Span,
+        simple_name: Span,
+        type_params: Span,
+    },
+    Tuple {
+        type_params: Span,
+    },
+    Trait {
+        /// Indices of `TraitBound` nodes.
+        bounds: Span,
+        lifetime_bounds: Span,
+        parenthesized: bool,
+    },
+    TraitBound {
+        bound_lifetimes: Span,
+        /// Index of a `Struct` node.
+        inner: NodeId,
+        fn_params: Option<Span>,
+        fn_return_type: Option<NodeId>,
+    },
+    Unit,
+    Unknown(Span),
+    Elided(Span),
+    Const(Span),
+    Lifetime(Span),
+    Binding {
+        name: Span,
+        type_name: NodeId,
+    },
+}
+
+/// Builds a `TypeNameFlat` while its type name string is parsed.
+///
+/// The elements of lists that are still being parsed are kept on stacks, and
+/// moved to the end of `children` or `segments` when the list is passed to
+/// the node that holds it, so that each list is contiguous.
+struct FlatBuilder<'s> {
+    /// Type name string that is being parsed.
+    text: &'s str,
+    /// Nodes built so far.
+    nodes: Vec<Node>,
+    /// Segments of built nodes.
+    segments: Vec<Span>,
+    /// Children of built nodes.
+    children: Vec<NodeId>,
+    /// Elements of node lists that are being parsed.
+    list_nodes: Vec<NodeId>,
+    /// Lifetimes of lifetime lists that are being parsed.
+    list_lifetimes: Vec<Span>,
+}
+
+/// Lengths of a `FlatBuilder`'s `Vec`s, which it can be rolled back to.
+#[derive(Clone, Copy, Debug, Default)]
+struct Checkpoint {
+    nodes: usize,
+    segments: usize,
+    children: usize,
+    list_nodes: usize,
+    list_lifetimes: usize,
+}
+
+/// Index that a list's elements begin at, in the stack of elements of lists
+/// that are being parsed.
+struct ListStart(usize);
+
+impl<'s> FlatBuilder<'s> {
+    /// Returns a new `FlatBuilder` for parsing the given type name string.
+    fn new(text: &'s str) -> Self {
+        Self {
+            text,
+            nodes: Vec::new(),
+            segments: Vec::new(),
+            children: Vec::new(),
+            list_nodes: Vec::new(),
+            list_lifetimes: Vec::new(),
+        }
+    }
+
+    /// Returns the built `TypeNameFlat`.
+    fn finish(self, root: NodeId) -> TypeNameFlat<'s> {
+        TypeNameFlat {
+            text: Cow::Borrowed(self.text),
+            nodes: self.nodes,
+            segments: self.segments,
+            children: self.children,
+            root,
+        }
+    }
+
+    /// Appends the node and returns its index.
+    fn push_node(&mut self, node: Node) -> NodeId {
+        let id = to_index(self.nodes.len());
+        self.nodes.push(node);
+
+        id
+    }
+
+    /// Returns the span of the text, which must be a slice of the parsed type
+    /// name string.
+    fn span(&self, text: &str) -> Span {
+        let start = text.as_ptr().addr() - self.text.as_ptr().addr();
+        debug_assert!(start + text.len() <= self.text.len());
+
+        Span::new(start, start + text.len())
+    }
+
+    /// Appends the segments of a `::` separated path, and returns their span.
+    fn push_path(&mut self, path: &str) -> Span {
+        let start = self.segments.len();
+        parser::path_segments(path).for_each(|segment| {
+            let span = self.span(segment);
+            self.segments.push(span);
+        });
+
+        Span::new(start, self.segments.len())
+    }
+
+    /// Moves the elements of a node list to `children`, and returns their
+    /// span.
+    fn finish_nodes(&mut self, list: ListStart) -> Span {
+        let start = self.children.len();
+        self.children.extend(self.list_nodes.drain(list.0..));
+
+        Span::new(start, self.children.len())
+    }
+
+    /// Moves the elements of a lifetime list to `segments`, and returns their
+    /// span.
+    fn finish_lifetimes(&mut self, list: ListStart) -> Span {
+        let start = self.segments.len();
+        self.segments.extend(self.list_lifetimes.drain(list.0..));
+
+        Span::new(start, self.segments.len())
+    }
+}
+
+impl<'s> Builder<'s> for FlatBuilder<'s> {
+    type Checkpoint = Checkpoint;
+    type GenericArg = NodeId;
+    type GenericArgs = ListStart;
+    type Lifetimes = ListStart;
+    type Struct = NodeId;
+    type TraitBound = NodeId;
+    type TraitBounds = ListStart;
+    type Type = NodeId;
+    type Types = ListStart;
+
+    fn checkpoint(&self) -> Self::Checkpoint {
+        Checkpoint {
+            nodes: self.nodes.len(),
+            segments: self.segments.len(),
+            children: self.children.len(),
+            list_nodes: self.list_nodes.len(),
+            list_lifetimes: self.list_lifetimes.len(),
+        }
+    }
+
+    fn rollback(&mut self, checkpoint: Self::Checkpoint) {
+        self.nodes.truncate(checkpoint.nodes);
+        self.segments.truncate(checkpoint.segments);
+        self.children.truncate(checkpoint.children);
+        self.list_nodes.truncate(checkpoint.list_nodes);
+        self.list_lifetimes.truncate(checkpoint.list_lifetimes);
+    }
+
+    fn types(&mut self) -> Self::Types {
+        ListStart(self.list_nodes.len())
+    }
+
+    fn push_type(&mut self, _types: &mut Self::Types, type_name: Self::Type) {
+        self.list_nodes.push(type_name);
+    }
+
+    fn generic_args(&mut self) -> Self::GenericArgs {
+        ListStart(self.list_nodes.len())
+    }
+
+    fn push_generic_arg(
+        &mut self,
+        _generic_args: &mut Self::GenericArgs,
+        generic_arg: Self::GenericArg,
+    ) {
+        self.list_nodes.push(generic_arg);
+    }
+
+    fn trait_bounds(&mut self) -> Self::TraitBounds {
+        ListStart(self.list_nodes.len())
+    }
+
+    fn push_trait_bound(&mut self, _bounds: &mut Self::TraitBounds, bound: Self::TraitBound) {
+        self.list_nodes.push(bound);
+    }
+
+    fn lifetimes(&mut self) -> Self::Lifetimes {
+        ListStart(self.list_lifetimes.len())
+    }
+
+    fn push_lifetime(&mut self, _lifetimes: &mut Self::Lifetimes, lifetime: &'s str) {
+        let span = self.span(lifetime);
+        self.list_lifetimes.push(span);
+    }
+
+    fn none(&mut self) -> Self::Type {
+        self.push_node(Node::None)
+    }
+
+    fn never(&mut self) -> Self::Type {
+        self.push_node(Node::Never)
+    }
+
+    fn unit(&mut self) -> Self::Type {
+        self.push_node(Node::Unit)
+    }
+
+    fn unknown(&mut self, text: &'s str) -> Self::Type {
+        let text = self.span(text);
+        self.push_node(Node::Unknown(text))
+    }
+
+    fn elided(&mut self, text: &'s str) -> Self::Type {
+        let text = self.span(text);
+        self.push_node(Node::Elided(text))
+    }
+
+    fn array(&mut self, type_param: Self::Type, len: &'s str) -> Self::Type {
+        let len = self.span(len);
+        self.push_node(Node::Array { type_param, len })
+    }
+
+    fn slice(&mut self, type_param: Self::Type) -> Self::Type {
+        self.push_node(Node::Slice { type_param })
+    }
+
+    fn reference(
+        &mut self,
+        lifetime: Option<&'s str>,
+        mutable: bool,
+        type_param: Self::Type,
+    ) -> Self::Type {
+        let lifetime = lifetime.map(|lifetime| self.span(lifetime));
+        self.push_node(Node::Reference {
+            lifetime,
+            mutable,
+            type_param,
+        })
+    }
+
+    fn pointer(&mut self, const_or_mut: &'s str, type_param: Self::Type) -> Self::Type {
+        let const_or_mut = self.span(const_or_mut);
+        self.push_node(Node::Pointer {
+            const_or_mut,
+            type_param,
+        })
+    }
+
+    fn function(
+        &mut self,
+        bound_lifetimes: Self::Lifetimes,
+        is_unsafe: bool,
+        abi: Option<&'s str>,
+        params: Self::Types,
+        variadic: bool,
+        return_type: Option<Self::Type>,
+    ) -> Self::Type {
+        let bound_lifetimes = self.finish_lifetimes(bound_lifetimes);
+        let abi = abi.map(|abi| self.span(abi));
+        let params = self.finish_nodes(params);
+        self.push_node(Node::Function {
+            bound_lifetimes,
+            is_unsafe,
+            abi,
+            params,
+            variadic,
+            return_type,
+        })
+    }
+
+    fn qualified_path(
+        &mut self,
+        self_type: Self::Type,
+        trait_path: Option<Self::Struct>,
+        assoc_path: &'s str,
+    ) -> Self::Type {
+        let assoc_path = self.push_path(assoc_path);
+        self.push_node(Node::QualifiedPath {
+            self_type,
+            trait_path,
+            assoc_path,
+        })
+    }
+
+    fn struct_path(
+        &mut self,
+        module_path: &'s str,
+        simple_name: &'s str,
+        type_params: Option<Self::GenericArgs>,
+    ) -> Self::Struct {
+        let module_path = self.push_path(module_path);
+        let simple_name = self.span(simple_name);
+        let type_params =
+            type_params.map_or(Span::EMPTY, |type_params| self.finish_nodes(type_params));
+        self.push_node(Node::Struct {
+            module_path,
+            simple_name,
+            type_params,
+        })
+    }
+
+    fn struct_type(&mut self, type_name_struct: Self::Struct) -> Self::Type {
+        type_name_struct
+    }
+
+    fn closure(
+        &mut self,
+        kind: ClosureKind,
+        parent_path: Self::Types,
+        module_path: &'s str,
+        segment: &'s str,
+        enclosing_path: &'s str,
+    ) -> Self::Type {
+        let parent_path = self.finish_nodes(parent_path);
+        let module_path = self.push_path(module_path);
+        let segment = self.span(segment);
+        let enclosing_path = self.push_path(enclosing_path);
+        self.push_node(Node::Closure {
+            kind,
+            parent_path,
+            module_path,
+            segment,
+            enclosing_path,
+        })
+    }
+
+    fn tuple(&mut self, type_params: Self::Types) -> Self::Type {
+        let type_params = self.finish_nodes(type_params);
+        self.push_node(Node::Tuple { type_params })
+    }
+
+    fn trait_object(
+        &mut self,
+        bounds: Self::TraitBounds,
+        lifetime_bounds: Self::Lifetimes,
+        parenthesized: bool,
+    ) -> Self::Type {
+        let bounds = self.finish_nodes(bounds);
+        let lifetime_bounds = self.finish_lifetimes(lifetime_bounds);
+        self.push_node(Node::Trait {
+            bounds,
+            lifetime_bounds,
+            parenthesized,
+        })
+    }
+
+    fn trait_bound(
+        &mut self,
+        bound_lifetimes: Self::Lifetimes,
+        inner: Self::Struct,
+        fn_params: Option<Self::Types>,
+        fn_return_type: Option<Self::Type>,
+    ) -> Self::TraitBound {
+        let bound_lifetimes = self.finish_lifetimes(bound_lifetimes);
+        let fn_params = fn_params.map(|fn_params| self.finish_nodes(fn_params));
+        self.push_node(Node::TraitBound {
+            bound_lifetimes,
+            inner,
+            fn_params,
+            fn_return_type,
+        })
+    }
+
+    fn type_arg(&mut self, type_name: Self::Type) -> Self::GenericArg {
+        type_name
+    }
+
+    fn const_arg(&mut self, value: &'s str) -> Self::GenericArg {
+        let value = self.span(value);
+        self.push_node(Node::Const(value))
+    }
+
+    fn lifetime_arg(&mut self, lifetime: &'s str) -> Self::GenericArg {
+        let lifetime = self.span(lifetime);
+        self.push_node(Node::Lifetime(lifetime))
+    }
+
+    fn binding_arg(&mut self, name: &'s str, type_name: Self::Type) -> Self::GenericArg {
+        let name = self.span(name);
+        self.push_node(Node::Binding { name, type_name })
+    }
+}
+
+/// Returns the index as a `u32`.
+///
+/// # Panics
+///
+/// Panics if the index does not fit, which would need a type name longer than
+/// four gigabytes.
+fn to_index(index: usize) -> u32 {
+    u32::try_from(index).expect("`TypeNameFlat` indices fit in a `u32`.")
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::{borrow::Cow, string::String, vec::Vec};
+    use core::{
+        alloc::{GlobalAlloc, Layout},
+        cell::Cell,
+    };
+    use std::alloc::System;
+
+    use super::{TypeNameFlat, TypeNameFlatKind};
+    use crate::{
+        render::{self, RenderContext},
+        GenericArg, TypeName, TypeNameFmt, TypeParamsFmtOpts,
+    };
+
+    /// Allocator that counts the allocations made by each thread, so that
+    /// tests running in parallel do not affect each other's counts.
+    struct CountingAllocator;
+
+    std::thread_local! {
+        /// Number of allocations made by this thread.
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    // SAFETY: Allocation is delegated to `System`.
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            // The counter is unavailable while thread locals are destroyed.
+            let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+            // SAFETY: The caller upholds `GlobalAlloc::alloc`'s contract.
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            // SAFETY: The caller upholds `GlobalAlloc::dealloc`'s contract.
+            unsafe { System.dealloc(ptr, layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+            // SAFETY: The caller upholds `GlobalAlloc::realloc`'s contract.
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// Returns the value returned by `f`, and the number of allocations that
+    /// this thread made while running it.
+    fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+        let before = ALLOCATIONS.with(Cell::get);
+        let value = f();
+        let after = ALLOCATIONS.with(Cell::get);
+
+        (value, after - before)
+    }
+
+    const STD_TYPE_NAMES: &[&str] = &[
+        "core::option::Option<alloc::string::String>",
+        "core::result::Result<alloc::vec::Vec<(u8, &'_ str)>, alloc::boxed::Box<dyn \
+        core::error::Error + core::marker::Send + 'static>>",
+        "alloc::boxed::Box<dyn for<'a> core::ops::function::Fn(&'a str) -> [u8; 3]>",
+        "<alloc::vec::Vec<u8> as core::iter::traits::collect::IntoIterator>::IntoIter",
+        "for<'a> unsafe extern \"C\" fn(&'a u8, *const [u8], ...) -> !",
+        "my_crate::Foo<'_, 16, Item = u8, %weird%>",
+        "my_crate::main::{closure#0}",
+        "my_crate::main::{{closure}}::{{closure}}",
+        "my_crate::Foo<u8>::run<u16>::{{closure}}",
+        "<my_crate::Foo<u8> as my_crate::Tr>::run::{{closure}}",
+        "<my_crate::Foo<u8> as my_crate::Tr>::run<i8>::{{closure}}",
+        "{async fn body of my_crate::run()}",
+        "&mut (dyn core::any::Any + core::marker::Send)",
+        "alloc::boxed::Box<dyn 'static>",
+        "(u8,)",
+        "()",
+        "core::option::Option<(u8, u16",
+    ];
+
+    #[test]
+    fn round_trips_type_names() {
+        STD_TYPE_NAMES.iter().for_each(|std_type_name| {
+            let type_name = TypeName::from(*std_type_name);
+            let type_name_flat = TypeNameFlat::from(*std_type_name);
+
+            assert_eq!(type_name, type_name_flat.to_type_name());
+            assert_eq!(type_name.as_str(), type_name_flat.as_str());
+            assert_eq!(type_name, type_name_flat.into_owned().to_type_name());
+        });
+    }
+
+    #[test]
+    fn renders_same_as_type_name() {
+        let fmts = [
+            TypeNameFmt::new(),
+            TypeNameFmt::full(),
+            TypeNameFmt::mn_opts(1, 1, TypeParamsFmtOpts::Std),
+            TypeNameFmt::mn_opts(0, 0, TypeParamsFmtOpts::None),
+            TypeNameFmt::new()
+                .segment_count_left(1)
+                .public_std_paths(true)
+                .elide_auto_traits(true),
+        ];
+
+        STD_TYPE_NAMES.iter().for_each(|std_type_name| {
+            let type_name = TypeName::from(*std_type_name);
+            let type_name_flat = TypeNameFlat::from(*std_type_name);
+
+            fmts.iter().for_each(|fmt| {
+                assert_eq!(
+                    type_name.as_str_with(fmt),
+                    type_name_flat.as_str_with(fmt),
+                    "{std_type_name}"
+                );
+            });
+
+            let mut full_string = String::new();
+            render::write_node_with(
+                type_name_flat.root(),
+                &mut full_string,
+                &TypeNameFmt::full(),
+                RenderContext::VERBATIM,
+            )
+            .expect("Writing to a `String` never fails.");
+            assert_eq!(type_name.to_full_string(), full_string, "{std_type_name}");
+        });
+    }
+
+    #[test]
+    fn allocates_less_than_type_name() {
+        let std_type_name = "core::result::Result<std::collections::hash::map::HashMap<\
+            alloc::string::String, alloc::vec::Vec<(u8, &'_ str, [u16; 4])>>, \
+            alloc::boxed::Box<dyn core::error::Error + core::marker::Send + core::marker::Sync \
+            + 'static>>";
+
+        let (type_name, type_name_allocations) =
+            count_allocations(|| TypeName::from(std_type_name));
+        let (type_name_flat, flat_allocations) =
+            count_allocations(|| TypeNameFlat::from(std_type_name));
+        assert!(
+            flat_allocations < type_name_allocations,
+            "`TypeNameFlat` made {flat_allocations} allocations, `TypeName` made \
+            {type_name_allocations}."
+        );
+
+        // The owned text is a single `String`.
+        let (type_name_owned, type_name_owned_allocations) =
+            count_allocations(|| type_name.clone().into_owned());
+        let (type_name_flat, flat_owned_allocations) =
+            count_allocations(|| type_name_flat.into_owned());
+        assert_eq!(1, flat_owned_allocations);
+        assert!(flat_owned_allocations < type_name_owned_allocations);
+
+        // Rendering reads the nodes directly, so writing to a buffer that does
+        // not allocate does not allocate at all.
+        let mut buffer = String::with_capacity(std_type_name.len());
+        let ((), render_allocations) = count_allocations(|| {
+            type_name_flat
+                .write_str_with(&mut buffer, &TypeNameFmt::new())
+                .expect("Writing to a `String` never fails.");
+        });
+        assert_eq!(0, render_allocations);
+        assert_eq!(type_name_owned.as_str(), buffer);
+    }
+
+    #[test]
+    fn reads_nodes() {
+        let type_name_flat = TypeNameFlat::from(
+            "alloc::boxed::Box<dyn core::iter::traits::iterator::Iterator<Item = [u8; 4]>>",
+        );
+
+        let root = type_name_flat.root();
+        assert_eq!(TypeNameFlatKind::Struct, root.kind());
+        assert_eq!(
+            ["alloc", "boxed"],
+            root.module_path().collect::<Vec<_>>().as_slice()
+        );
+        assert_eq!("Box", root.simple_name());
+
+        let trait_object = root.type_params().next().unwrap();
+        assert_eq!(TypeNameFlatKind::Trait, trait_object.kind());
+        assert_eq!("Iterator", trait_object.simple_name());
+        assert_eq!(4, trait_object.module_path().len());
+
+        let binding = trait_object.type_params().next().unwrap();
+        assert_eq!(TypeNameFlatKind::Binding, binding.kind());
+        match binding.to_generic_arg() {
+            GenericArg::Binding(type_name_binding) => {
+                assert_eq!("Item", type_name_binding.name());
+                assert_eq!("[u8; 4]", type_name_binding.type_name().as_str());
+            }
+            generic_arg => panic!("Expected a binding, got `{generic_arg:?}`."),
+        }
+        assert_eq!(
+            TypeName::Unknown(Cow::Borrowed("Item = [u8; 4]")),
+            binding.to_type_name()
+        );
+
+        let kinds = [root, trait_object, binding]
+            .iter()
+            .map(|node| node.type_params().len())
+            .collect::<Vec<_>>();
+        assert_eq!([1, 1, 0], kinds.as_slice());
+        assert_eq!(None, root.type_param());
+    }
+}
@@ -0,0 +1,358 @@
+use crate::{std_paths, TypeParamsFmtOpts};
+
+/// Options for formatting a `TypeName`.
+///
+/// By default, type names are formatted without module paths, with all type
+/// parameters. Use the builder methods to change this.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use tynm::{TypeName, TypeNameFmt, TypeParamsFmtOpts};
+///
+/// let fmt = TypeNameFmt::new()
+///     .segment_count_left(1)
+///     .type_params_fmt_opts(TypeParamsFmtOpts::Std);
+///
+/// assert_eq!(
+///     tynm::type_name_with::<Option<String>>(&fmt),
+///     "core::..::Option<alloc::..::String>"
+/// );
+///
+/// let tn = TypeName::new::<Option<String>>();
+/// assert_eq!(
+///     tn.display(&TypeNameFmt::new().segment_count_right(1))
+///         .to_string(),
+///     "..::option::Option<..::string::String>"
+/// );
+/// # }
+/// #
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TypeNameFmt {
+    /// Number of module segments to include, beginning from the left (most
+    /// significant).
+    pub(crate) segment_count_left: usize,
+    /// Number of module segments to include, beginning from the right (least
+    /// significant).
+    pub(crate) segment_count_right: usize,
+    /// How to format type parameters.
+    pub(crate) type_params_fmt_opts: TypeParamsFmtOpts,
+    /// Crates whose types are written with type parameters in
+    /// `TypeParamsFmtOpts::Std` mode.
+    pub(crate) std_crates: StdCrates,
+    /// Whether to elide auto traits such as `Send` and `Sync` from trait
+    /// objects.
+    pub(crate) elide_auto_traits: bool,
+    /// Whether module paths under `core` and `alloc` are written under `std`.
+    pub(crate) normalize_std_paths: bool,
+    /// Whether standard library types are written with the public module path
+    /// that they are re-exported from, instead of their definition module.
+    pub(crate) public_std_paths: bool,
+}
+
+impl TypeNameFmt {
+    /// Returns `TypeNameFmt` that formats type names without module paths,
+    /// with all type parameters.
+    pub const fn new() -> Self {
+        Self::mn_opts(0, 0, TypeParamsFmtOpts::All)
+    }
+
+    /// Sets the number of module segments to include, beginning from the left
+    /// (most significant).
+    ///
+    /// If the left and right module segments overlap, the overlapping segments
+    /// are only written once.
+    pub const fn segment_count_left(mut self, segment_count_left: usize) -> Self {
+        self.segment_count_left = segment_count_left;
+        self
+    }
+
+    /// Sets the number of module segments to include, beginning from the right
+    /// (least significant).
+    ///
+    /// If the left and right module segments overlap, the overlapping segments
+    /// are only written once.
+    pub const fn segment_count_right(mut self, segment_count_right: usize) -> Self {
+        self.segment_count_right = segment_count_right;
+        self
+    }
+
+    /// Sets how to format type parameters, see [`TypeParamsFmtOpts`] for
+    /// details.
+    pub const fn type_params_fmt_opts(mut self, type_params_fmt_opts: TypeParamsFmtOpts) -> Self {
+        self.type_params_fmt_opts = type_params_fmt_opts;
+        self
+    }
+
+    /// Sets the crates whose types are written with type parameters in
+    /// [`TypeParamsFmtOpts::Std`] mode.
+    ///
+    /// Defaults to `["std", "core", "alloc"]`. Include these to keep type
+    /// parameters of standard library types.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "alloc")]
+    /// # fn main() {
+    /// use tynm::{TypeName, TypeNameFmt, TypeParamsFmtOpts};
+    ///
+    /// let tn = TypeName::from("hashbrown::map::HashMap<my_crate::Key<u8>, u8>");
+    ///
+    /// let fmt = TypeNameFmt::new().type_params_fmt_opts(TypeParamsFmtOpts::Std);
+    /// assert_eq!(tn.as_str_with(&fmt), "HashMap");
+    ///
+    /// let fmt = fmt.std_crates(&["std", "core", "alloc", "hashbrown"]);
+    /// assert_eq!(tn.as_str_with(&fmt), "HashMap<Key, u8>");
+    /// # }
+    /// #
+    /// # #[cfg(not(feature = "alloc"))]
+    /// # fn main() {}
+    /// ```
+    pub const fn std_crates(mut self, std_crates: &'static [&'static str]) -> Self {
+        self.std_crates = StdCrates::Names(std_crates);
+        self
+    }
+
+    /// Sets the function that returns whether types from a crate are written
+    /// with type parameters in [`TypeParamsFmtOpts::Std`] mode.
+    ///
+    /// The function is passed the crate name, which is the first segment of
+    /// the type's module path. This replaces the crates set by
+    /// [`std_crates`].
+    ///
+    /// Options are compared by the function pointer's address, which is not
+    /// guaranteed to be the same for every use of the same function. Type name
+    /// caches may therefore store more than one entry for the same function.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "alloc")]
+    /// # fn main() {
+    /// use tynm::{TypeName, TypeNameFmt, TypeParamsFmtOpts};
+    ///
+    /// let tn = TypeName::from("my_foundation_vec::SmallVec<my_crate::Item<u8>>");
+    ///
+    /// let fmt = TypeNameFmt::new()
+    ///     .type_params_fmt_opts(TypeParamsFmtOpts::Std)
+    ///     .std_crates_fn(|crate_name| crate_name.starts_with("my_foundation_"));
+    /// assert_eq!(tn.as_str_with(&fmt), "SmallVec<Item>");
+    /// # }
+    /// #
+    /// # #[cfg(not(feature = "alloc"))]
+    /// # fn main() {}
+    /// ```
+    ///
+    /// [`std_crates`]: Self::std_crates
+    pub const fn std_crates_fn(mut self, is_std_crate: fn(&str) -> bool) -> Self {
+        self.std_crates = StdCrates::Fn(is_std_crate);
+        self
+    }
+
+    /// Sets whether auto traits are elided from trait objects.
+    ///
+    /// When enabled, `Send`, `Sync`, `Unpin`, `UnwindSafe`, and
+    /// `RefUnwindSafe` bounds are not written, unless they are the only trait
+    /// in the trait object.
+    pub const fn elide_auto_traits(mut self, elide_auto_traits: bool) -> Self {
+        self.elide_auto_traits = elide_auto_traits;
+        self
+    }
+
+    /// Sets whether module paths under `core` and `alloc` are written under
+    /// `std`, e.g. `std::string::String` instead of `alloc::string::String`.
+    ///
+    /// `core::any::type_name` reports the crate that a type is defined in,
+    /// whereas the same types are usually referred to through `std`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tynm::{BoundedBuffer, TypeNameFmt};
+    ///
+    /// let mut bytes = [0u8; 64];
+    /// let mut buffer = BoundedBuffer::new(&mut bytes);
+    ///
+    /// let fmt = TypeNameFmt::new()
+    ///     .segment_count_left(1)
+    ///     .normalize_std_paths(true);
+    /// tynm::write_type_name_with::<Option<String>>(&mut buffer, &fmt).unwrap();
+    ///
+    /// assert_eq!(buffer.as_str(), "std::..::Option<std::..::String>");
+    /// ```
+    pub const fn normalize_std_paths(mut self, normalize_std_paths: bool) -> Self {
+        self.normalize_std_paths = normalize_std_paths;
+        self
+    }
+
+    /// Sets whether standard library types are written with the public module
+    /// path that they are re-exported from, e.g. `std::collections::HashMap`
+    /// instead of `std::collections::hash::map::HashMap`.
+    ///
+    /// `core::any::type_name` reports the module that a type is defined in,
+    /// which is often private. When enabled, the module path is looked up in a
+    /// built-in table of the `std`, `core`, and `alloc` public API, so that the
+    /// written path can be used in a `use` statement. This also writes paths
+    /// under `core` and `alloc` under `std`, as with
+    /// [`normalize_std_paths`](Self::normalize_std_paths).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::{collections::HashMap, ptr::NonNull};
+    ///
+    /// use tynm::{BoundedBuffer, TypeNameFmt};
+    ///
+    /// let mut bytes = [0u8; 128];
+    /// let mut buffer = BoundedBuffer::new(&mut bytes);
+    ///
+    /// let fmt = TypeNameFmt::new()
+    ///     .segment_count_left(usize::MAX)
+    ///     .public_std_paths(true);
+    /// tynm::write_type_name_with::<HashMap<u8, NonNull<Vec<u8>>>>(&mut buffer, &fmt).unwrap();
+    ///
+    /// assert_eq!(
+    ///     buffer.as_str(),
+    ///     "std::collections::HashMap<u8, std::ptr::NonNull<std::vec::Vec<u8>>>"
+    /// );
+    /// ```
+    pub const fn public_std_paths(mut self, public_std_paths: bool) -> Self {
+        self.public_std_paths = public_std_paths;
+        self
+    }
+
+    /// Returns new `TypeNameFmt` with the given segment counts and type
+    /// parameter formatting options.
+    pub(crate) const fn mn_opts(
+        segment_count_left: usize,
+        segment_count_right: usize,
+        type_params_fmt_opts: TypeParamsFmtOpts,
+    ) -> Self {
+        Self {
+            segment_count_left,
+            segment_count_right,
+            type_params_fmt_opts,
+            std_crates: StdCrates::DEFAULT,
+            elide_auto_traits: false,
+            normalize_std_paths: false,
+            public_std_paths: false,
+        }
+    }
+
+    /// Returns `TypeNameFmt` that formats type names with full module paths,
+    /// which reproduces the `core::any::type_name` string when closures and
+    /// elided types are also written verbatim.
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn full() -> Self {
+        Self::mn_opts(usize::MAX, 0, TypeParamsFmtOpts::All)
+    }
+
+    /// Returns whether types from the crate are written with type parameters
+    /// in `TypeParamsFmtOpts::Std` mode.
+    pub(crate) fn is_std_crate(&self, crate_name: &str) -> bool {
+        self.std_crates.contains(crate_name)
+    }
+
+    /// Returns the crate name to write for the given crate.
+    ///
+    /// This is `"std"` for `core` and `alloc` when normalizing standard library
+    /// paths, or writing their public paths.
+    pub(crate) fn crate_name<'c>(&self, crate_name: &'c str) -> &'c str {
+        match crate_name {
+            "core" | "alloc" if self.normalize_std_paths || self.public_std_paths => "std",
+            _ => crate_name,
+        }
+    }
+
+    /// Returns the public module path to write in place of the type's module
+    /// path, if writing public standard library paths.
+    ///
+    /// # Parameters
+    ///
+    /// * `module_path`: Module path segments that the type is defined in.
+    /// * `simple_name`: Name of the type.
+    pub(crate) fn public_module_path<'p, I>(
+        &self,
+        module_path: I,
+        simple_name: &str,
+    ) -> Option<&'static [&'static str]>
+    where
+        I: IntoIterator<Item = &'p str>,
+        I::IntoIter: Clone,
+    {
+        if self.public_std_paths {
+            std_paths::public_module_path(module_path, simple_name)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the trait bound is an auto trait that is elided from
+    /// trait objects.
+    ///
+    /// # Parameters
+    ///
+    /// * `crate_name`: First segment of the trait's module path, if any.
+    /// * `simple_name`: Name of the trait.
+    pub(crate) fn is_elided_auto_trait(&self, crate_name: Option<&str>, simple_name: &str) -> bool {
+        self.elide_auto_traits
+            && matches!(crate_name, None | Some("std" | "core" | "alloc"))
+            && matches!(
+                simple_name,
+                "Send" | "Sync" | "Unpin" | "UnwindSafe" | "RefUnwindSafe"
+            )
+    }
+}
+
+impl Default for TypeNameFmt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Crates whose types are written with type parameters in
+/// `TypeParamsFmtOpts::Std` mode.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum StdCrates {
+    /// Crates with the given names.
+    Names(&'static [&'static str]),
+    /// Crates whose names the function returns `true` for.
+    Fn(fn(&str) -> bool),
+}
+
+impl StdCrates {
+    /// The standard library crates.
+    const DEFAULT: Self = Self::Names(&["std", "core", "alloc"]);
+
+    /// Returns whether the crate is included.
+    fn contains(&self, crate_name: &str) -> bool {
+        match self {
+            Self::Names(names) => names.contains(&crate_name),
+            Self::Fn(is_std_crate) => is_std_crate(crate_name),
+        }
+    }
+}
+
+impl PartialEq for StdCrates {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Names(names), Self::Names(other_names)) => {
+                core::ptr::eq(*names, *other_names) || names == other_names
+            }
+            // The same function may have different addresses, in which case
+            // the options are not equal, and caches keep an entry for each.
+            (Self::Fn(is_std_crate), Self::Fn(other_is_std_crate)) => {
+                core::ptr::fn_addr_eq(*is_std_crate, *other_is_std_crate)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for StdCrates {}
@@ -1,10 +1,14 @@
 /// Specifies the way to output type parameters.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
 pub enum TypeParamsFmtOpts {
     /// Output all type parameters, with the `m`/`n` number of segments.
     All,
     /// Only output type parameters if the type is from the standard library.
     ///
+    /// The crates that count as the standard library can be set with
+    /// `TypeNameFmt::std_crates`.
+    ///
     /// # Examples
     ///
     /// * `MyStruct<SomeType>` returns `MyStruct`.
@@ -12,4 +16,75 @@ pub enum TypeParamsFmtOpts {
     /// * `Pin<Box<SomeType>>` returns `Pin<Box<SomeType>>`.
     /// * `Box<dyn MyTrait<SomeType>>` returns `Box<dyn MyTrait>`.
     Std,
+    /// Do not output type parameters.
+    ///
+    /// Tuple fields are still output, as they are not type parameters.
+    ///
+    /// # Examples
+    ///
+    /// * `Vec<SomeType>` returns `Vec`.
+    /// * `Result<(A, B), SomeError>` returns `Result`.
+    /// * `(Vec<A>, B)` returns `(Vec, B)`.
+    None,
+    /// Only output type parameters that are nested at most `max_depth` levels
+    /// deep. Deeper type parameters are replaced with `marker`.
+    ///
+    /// Tuple fields count as a level of type parameters.
+    ///
+    /// # Examples
+    ///
+    /// With `max_depth: 1` and `marker: ".."`:
+    ///
+    /// * `Result<Vec<A>, E<B>>` returns `Result<Vec<..>, E<..>>`.
+    /// * `Option<(A, B)>` returns `Option<(..)>`.
+    /// * `Vec<A>` returns `Vec<A>`.
+    MaxDepth {
+        /// Maximum number of nested type parameter levels to output.
+        max_depth: usize,
+        /// Text written in place of elided type parameters.
+        marker: &'static str,
+    },
+    /// Only output the first `max_params` type parameters of each type. If
+    /// there are more, they are replaced with `marker`.
+    ///
+    /// Tuple fields count as type parameters.
+    ///
+    /// # Examples
+    ///
+    /// With `max_params: 2` and `marker: "…"`:
+    ///
+    /// * `SomeType<A, B, C>` returns `SomeType<A, B, …>`.
+    /// * `(A, B, C, D)` returns `(A, B, …)`.
+    /// * `HashMap<K, V>` returns `HashMap<K, V>`.
+    MaxParams {
+        /// Maximum number of type parameters to output for each type.
+        max_params: usize,
+        /// Text written in place of the remaining type parameters.
+        marker: &'static str,
+    },
+}
+
+impl TypeParamsFmtOpts {
+    /// Returns how many of a type's parameters to output, and the marker to
+    /// write in place of the rest, if any.
+    ///
+    /// # Parameters
+    ///
+    /// * `depth`: Number of type parameter levels that the type is nested in.
+    /// * `count`: Number of type parameters of the type.
+    pub(crate) fn type_params_shown(
+        self,
+        depth: usize,
+        count: usize,
+    ) -> (usize, Option<&'static str>) {
+        match self {
+            Self::MaxDepth { max_depth, marker } if count > 0 && depth >= max_depth => {
+                (0, Some(marker))
+            }
+            Self::MaxParams { max_params, marker } if count > max_params => {
+                (max_params, Some(marker))
+            }
+            _ => (count, None),
+        }
+    }
 }
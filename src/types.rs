@@ -1,10 +1,18 @@
-use alloc::{boxed::Box, string::String, vec::Vec};
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
 use core::{
     fmt,
     fmt::{Error, Write},
+    str::FromStr,
 };
 
-use crate::{parser, TypeParamsFmtOpts};
+use crate::{
+    closure_kind::ClosureKind,
+    parser::{self, Nesting, TreeBuilder},
+    render::{self, NodeView, RenderContext, RenderNode},
+    stream,
+    type_name_fmt::TypeNameFmt,
+    ParseError, ParseLimits, TypeParamsFmtOpts,
+};
 
 /// Helper struct for printing type names directly to `format!`.
 ///
@@ -24,19 +32,84 @@ use crate::{parser, TypeParamsFmtOpts};
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TypeNameDisplay<'s> {
     inner: &'s TypeName<'s>,
-    segment_count_left: usize,
-    segment_count_right: usize,
-    type_params_fmt_opts: TypeParamsFmtOpts,
+    fmt: TypeNameFmt,
+    context: RenderContext,
+}
+
+impl TypeNameDisplay<'_> {
+    /// Sets whether auto traits are elided from trait objects.
+    ///
+    /// When enabled, `Send`, `Sync`, `Unpin`, `UnwindSafe`, and
+    /// `RefUnwindSafe` bounds are not printed, unless they are the only trait
+    /// in the trait object.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tynm::TypeName;
+    ///
+    /// type BoxError = Box<dyn std::error::Error + Send + Sync>;
+    /// let tn = TypeName::new::<BoxError>();
+    ///
+    /// assert_eq!(
+    ///     tn.as_display().elide_auto_traits(true).to_string(),
+    ///     "Box<dyn Error>"
+    /// );
+    /// ```
+    pub fn elide_auto_traits(mut self, elide_auto_traits: bool) -> Self {
+        self.fmt.elide_auto_traits = elide_auto_traits;
+        self
+    }
 }
 
 impl fmt::Display for TypeNameDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.inner.write_str_opts(
-            f,
-            self.segment_count_left,
-            self.segment_count_right,
-            self.type_params_fmt_opts,
-        )
+        self.inner.write_str_context(f, &self.fmt, self.context)
+    }
+}
+
+/// Helper struct for printing an owned type name directly to `format!`.
+///
+/// Unlike `TypeNameDisplay`, this owns the `TypeName`, so it may be stored in
+/// long-lived structs or error types. It can be obtained by the `into_display`
+/// and `into_display_mn_opts` methods.
+///
+/// # Example
+///
+/// ```rust
+/// use tynm::{TypeName, TypeNameDisplayOwned};
+///
+/// fn parse_display(std_type_name: &str) -> TypeNameDisplayOwned<'static> {
+///     TypeName::from(std_type_name).into_owned().into_display()
+/// }
+///
+/// let display = parse_display("alloc::vec::Vec<u8>");
+/// assert_eq!("Vec<u8>", display.to_string());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeNameDisplayOwned<'s> {
+    inner: TypeName<'s>,
+    fmt: TypeNameFmt,
+}
+
+impl<'s> TypeNameDisplayOwned<'s> {
+    /// Returns the type name that is displayed.
+    pub fn type_name(&self) -> &TypeName<'s> {
+        &self.inner
+    }
+
+    /// Sets whether auto traits are elided from trait objects.
+    ///
+    /// See [`TypeNameDisplay::elide_auto_traits`] for details.
+    pub fn elide_auto_traits(mut self, elide_auto_traits: bool) -> Self {
+        self.fmt.elide_auto_traits = elide_auto_traits;
+        self
+    }
+}
+
+impl fmt::Display for TypeNameDisplayOwned<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.write_str_fmt(f, &self.fmt)
     }
 }
 
@@ -45,15 +118,298 @@ impl fmt::Display for TypeNameDisplay<'_> {
 pub enum TypeName<'s> {
     None,
     Array(TypeNameArray<'s>),
-    // Function(TypeNameFunction<'s>), // TODO
+    Closure(TypeNameClosure<'s>),
+    Function(TypeNameFunction<'s>),
     Never,
     Pointer(TypeNamePointer<'s>),
+    QualifiedPath(TypeNameQualifiedPath<'s>),
     Reference(TypeNameReference<'s>),
     Slice(TypeNameSlice<'s>),
     Struct(TypeNameStruct<'s>),
     Tuple(TypeNameTuple<'s>),
     Trait(TypeNameTrait<'s>),
     Unit,
+    /// Verbatim text that could not be parsed, rendered unchanged.
+    Unknown(Cow<'s, str>),
+    /// Verbatim text that was not parsed because it exceeded the
+    /// [`ParseLimits`], rendered as `..`.
+    ///
+    /// [`ParseLimits`]: crate::ParseLimits
+    Elided(Cow<'s, str>),
+}
+
+impl<'s> TypeName<'s> {
+    /// Parses a type name string, returning an error if it cannot be parsed.
+    ///
+    /// Unparseable generic arguments, tuple fields, and function parameters
+    /// are captured as [`TypeName::Unknown`]. Text after the first complete
+    /// type name is ignored, see [`TypeName::parse_strict`] to reject it.
+    ///
+    /// # Parameters
+    ///
+    /// * `std_type_name`: Type name string, such as from
+    ///   `core::any::type_name`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tynm::TypeName;
+    ///
+    /// let type_name = TypeName::parse("alloc::vec::Vec<u8>").unwrap();
+    /// assert_eq!("Vec<u8>", type_name.as_str());
+    ///
+    /// assert!(TypeName::parse("%").is_err());
+    /// ```
+    pub fn parse(std_type_name: &'s str) -> Result<Self, ParseError> {
+        Self::parse_with_limits(std_type_name, &ParseLimits::default())
+    }
+
+    /// Parses a type name string, eliding types that exceed the given limits.
+    ///
+    /// [`TypeName::parse`] uses [`ParseLimits::default`]. See [`ParseLimits`]
+    /// for details.
+    ///
+    /// # Parameters
+    ///
+    /// * `std_type_name`: Type name string, such as from
+    ///   `core::any::type_name`.
+    /// * `limits`: Limits on how much of the type name to parse.
+    pub fn parse_with_limits(
+        std_type_name: &'s str,
+        limits: &ParseLimits,
+    ) -> Result<Self, ParseError> {
+        Self::parse_partial(std_type_name, limits).map(|(_input, type_name)| type_name)
+    }
+
+    /// Parses a type name string, returning an error if it cannot be parsed,
+    /// or if text remains after the type name.
+    ///
+    /// [`TypeName::parse`] stops at the first character it does not
+    /// understand, which renders a truncated name. This instead returns
+    /// [`ParseErrorKind::TrailingInput`] with the unconsumed span.
+    /// `TypeName::from` uses this, and captures the whole string as
+    /// [`TypeName::Unknown`] if it returns an error.
+    ///
+    /// # Parameters
+    ///
+    /// * `std_type_name`: Type name string, such as from
+    ///   `core::any::type_name`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tynm::{ParseErrorKind, TypeName};
+    ///
+    /// let type_name = TypeName::parse_strict("alloc::vec::Vec<u8>").unwrap();
+    /// assert_eq!("Vec<u8>", type_name.as_str());
+    ///
+    /// let error = TypeName::parse_strict("my_crate::Foo<u8").unwrap_err();
+    /// assert_eq!(ParseErrorKind::TrailingInput { len: 3 }, error.kind());
+    /// assert_eq!(13..16, error.span());
+    /// ```
+    ///
+    /// [`ParseErrorKind::TrailingInput`]: crate::ParseErrorKind::TrailingInput
+    pub fn parse_strict(std_type_name: &'s str) -> Result<Self, ParseError> {
+        Self::parse_strict_with_limits(std_type_name, &ParseLimits::default())
+    }
+
+    /// Parses a type name string, eliding types that exceed the given limits,
+    /// and returning an error if text remains after the type name.
+    ///
+    /// [`TypeName::parse_strict`] uses [`ParseLimits::default`]. See
+    /// [`ParseLimits`] for details.
+    ///
+    /// # Parameters
+    ///
+    /// * `std_type_name`: Type name string, such as from
+    ///   `core::any::type_name`.
+    /// * `limits`: Limits on how much of the type name to parse.
+    pub fn parse_strict_with_limits(
+        std_type_name: &'s str,
+        limits: &ParseLimits,
+    ) -> Result<Self, ParseError> {
+        let (remaining, type_name) = Self::parse_partial(std_type_name, limits)?;
+        if remaining.is_empty() {
+            Ok(type_name)
+        } else {
+            Err(ParseError::trailing(std_type_name, remaining))
+        }
+    }
+
+    /// Returns this type name with all strings owned, so that it does not
+    /// borrow the string it was parsed from.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tynm::TypeName;
+    ///
+    /// let type_name: TypeName<'static> = {
+    ///     let std_type_name = String::from("alloc::vec::Vec<u8>");
+    ///     TypeName::from(std_type_name.as_str()).into_owned()
+    /// };
+    ///
+    /// assert_eq!("Vec<u8>", type_name.as_str());
+    /// ```
+    pub fn into_owned(self) -> TypeName<'static> {
+        match self {
+            Self::None => TypeName::None,
+            Self::Array(type_name_array) => TypeName::Array(type_name_array.into_owned()),
+            Self::Closure(type_name_closure) => TypeName::Closure(type_name_closure.into_owned()),
+            Self::Function(type_name_function) => {
+                TypeName::Function(type_name_function.into_owned())
+            }
+            Self::Never => TypeName::Never,
+            Self::Pointer(type_name_pointer) => TypeName::Pointer(type_name_pointer.into_owned()),
+            Self::QualifiedPath(type_name_qualified_path) => {
+                TypeName::QualifiedPath(type_name_qualified_path.into_owned())
+            }
+            Self::Reference(type_name_reference) => {
+                TypeName::Reference(type_name_reference.into_owned())
+            }
+            Self::Slice(type_name_slice) => TypeName::Slice(type_name_slice.into_owned()),
+            Self::Struct(type_name_struct) => TypeName::Struct(type_name_struct.into_owned()),
+            Self::Tuple(type_name_tuple) => TypeName::Tuple(type_name_tuple.into_owned()),
+            Self::Trait(type_name_trait) => TypeName::Trait(type_name_trait.into_owned()),
+            Self::Unit => TypeName::Unit,
+            Self::Unknown(text) => TypeName::Unknown(cow_into_owned(text)),
+            Self::Elided(text) => TypeName::Elided(cow_into_owned(text)),
+        }
+    }
+
+    /// Rewrites module paths under `core` and `alloc` to their `std`
+    /// equivalents, e.g. `alloc::string::String` to `std::string::String`.
+    ///
+    /// This applies the same normalization as
+    /// [`TypeNameFmt::normalize_std_paths`] to the type name itself, so that
+    /// it is written with `std` paths in every rendering mode.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tynm::TypeName;
+    ///
+    /// let mut type_name = TypeName::new::<Option<String>>();
+    /// type_name.normalize();
+    ///
+    /// assert_eq!(
+    ///     type_name.to_full_string(),
+    ///     "std::option::Option<std::string::String>"
+    /// );
+    /// ```
+    pub fn normalize(&mut self) {
+        match self {
+            Self::Array(TypeNameArray { type_param, .. })
+            | Self::Pointer(TypeNamePointer { type_param, .. })
+            | Self::Reference(TypeNameReference { type_param, .. })
+            | Self::Slice(TypeNameSlice { type_param }) => type_param.normalize(),
+            Self::Closure(type_name_closure) => {
+                // Paths that follow a parent path do not begin with a crate
+                // name.
+                if type_name_closure.parent_path.is_empty() {
+                    normalize_module_path(&mut type_name_closure.module_path);
+                    normalize_module_path(&mut type_name_closure.enclosing_path);
+                } else {
+                    type_name_closure
+                        .parent_path
+                        .iter_mut()
+                        .for_each(Self::normalize);
+                }
+            }
+            Self::Function(type_name_function) => {
+                type_name_function
+                    .params
+                    .iter_mut()
+                    .for_each(Self::normalize);
+                if let Some(return_type) = type_name_function.return_type.as_deref_mut() {
+                    return_type.normalize();
+                }
+            }
+            Self::QualifiedPath(type_name_qualified_path) => {
+                type_name_qualified_path.self_type.normalize();
+                if let Some(trait_path) = type_name_qualified_path.trait_path.as_mut() {
+                    trait_path.normalize();
+                }
+            }
+            Self::Struct(type_name_struct) => type_name_struct.normalize(),
+            Self::Tuple(type_name_tuple) => {
+                type_name_tuple
+                    .type_params
+                    .iter_mut()
+                    .for_each(Self::normalize);
+            }
+            Self::Trait(type_name_trait) => {
+                type_name_trait
+                    .bounds
+                    .iter_mut()
+                    .for_each(TypeNameTraitBound::normalize);
+            }
+            Self::None | Self::Never | Self::Unit | Self::Unknown(_) | Self::Elided(_) => {}
+        }
+    }
+
+    /// Returns an object that owns this type name and implements
+    /// `fmt::Display` for printing it without any module paths.
+    ///
+    /// Unlike [`TypeName::as_display`], the returned object does not borrow
+    /// this type name, so it may be stored or returned from a function.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tynm::TypeName;
+    ///
+    /// let display = TypeName::new::<String>().into_display();
+    ///
+    /// assert_eq!("String", display.to_string());
+    /// ```
+    pub fn into_display(self) -> TypeNameDisplayOwned<'s> {
+        self.into_display_mn_opts(0, 0, TypeParamsFmtOpts::All)
+    }
+
+    /// Returns an object that owns this type name and implements
+    /// `fmt::Display` for printing it with the given number of module
+    /// segments.
+    ///
+    /// If the left and right module segments overlap, the overlapping segments
+    /// will only be printed once.
+    ///
+    /// # Parameters
+    ///
+    /// * `m`: Number of module segments to include, beginning from the left
+    ///   (most significant).
+    /// * `n`: Number of module segments to include, beginning from the right
+    ///   (least significant).
+    /// * `type_params_fmt_opts`: How to format type parameters, see the type
+    ///   documentation for details.
+    pub fn into_display_mn_opts(
+        self,
+        m: usize,
+        n: usize,
+        type_params_fmt_opts: TypeParamsFmtOpts,
+    ) -> TypeNameDisplayOwned<'s> {
+        self.into_display_with(&TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
+
+    /// Returns an object that owns this type name and implements
+    /// `fmt::Display` for printing it using the given format options.
+    pub fn into_display_with(self, fmt: &TypeNameFmt) -> TypeNameDisplayOwned<'s> {
+        TypeNameDisplayOwned {
+            inner: self,
+            fmt: *fmt,
+        }
+    }
+
+    /// Parses a type name from the start of the string, returning the
+    /// remaining input alongside it.
+    fn parse_partial(
+        std_type_name: &'s str,
+        limits: &ParseLimits,
+    ) -> Result<(&'s str, Self), ParseError> {
+        let nesting = Nesting::new(std_type_name, *limits);
+        parser::type_name(&mut TreeBuilder, std_type_name, nesting)
+            .map_err(|remaining| ParseError::new(std_type_name, remaining))
+    }
 }
 
 impl TypeName<'_> {
@@ -117,9 +473,55 @@ impl TypeName<'_> {
         n: usize,
         type_params_fmt_opts: TypeParamsFmtOpts,
     ) -> String {
-        let mut buffer = String::with_capacity(128); // TODO: smarter capacity allocation.
+        self.as_str_with(&TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
+
+    /// Returns the type name string with full module paths, identical to the
+    /// `core::any::type_name` string it was parsed from.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tynm::TypeName;
+    ///
+    /// let std_type_name = core::any::type_name::<Option<String>>();
+    /// let tn = TypeName::from(std_type_name);
+    ///
+    /// assert_eq!(tn.to_full_string(), std_type_name);
+    /// ```
+    pub fn to_full_string(&self) -> String {
+        self.as_str_context(&TypeNameFmt::full(), RenderContext::VERBATIM)
+    }
+
+    /// Returns the type name string using the given format options.
+    ///
+    /// The string is allocated once, with the exact rendered length.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tynm::{TypeName, TypeNameFmt};
+    ///
+    /// let tn = TypeName::new::<Option<String>>();
+    ///
+    /// assert_eq!(
+    ///     tn.as_str_with(&TypeNameFmt::new().segment_count_left(1)),
+    ///     "core::..::Option<alloc::..::String>"
+    /// );
+    /// ```
+    pub fn as_str_with(&self, fmt: &TypeNameFmt) -> String {
+        self.as_str_context(fmt, RenderContext::default())
+    }
 
-        self.write_str_opts(&mut buffer, m, n, type_params_fmt_opts)
+    /// Returns the type name string using the given format options and render
+    /// state.
+    fn as_str_context(&self, fmt: &TypeNameFmt, context: RenderContext) -> String {
+        let mut rendered_len = RenderedLen(0);
+        self.write_str_context(&mut rendered_len, fmt, context)
+            .expect("`RenderedLen` never returns an error.");
+        let mut buffer = String::with_capacity(rendered_len.0);
+
+        self.write_str_context(&mut buffer, fmt, context)
             .unwrap_or_else(|e| panic!("Failed to write `TypeName` as String. Error: `{}`.", e));
 
         buffer
@@ -160,12 +562,7 @@ impl TypeName<'_> {
     /// println!("{}", tn.as_display_opts(TypeParamsFmtOpts::Std));
     /// ```
     pub fn as_display_opts(&self, type_params_fmt_opts: TypeParamsFmtOpts) -> TypeNameDisplay<'_> {
-        TypeNameDisplay {
-            inner: self,
-            segment_count_left: 0,
-            segment_count_right: 0,
-            type_params_fmt_opts,
-        }
+        self.as_display_mn_opts(0, 0, type_params_fmt_opts)
     }
 
     /// Returns an object that implements `fmt::Display` for printing the type
@@ -232,11 +629,53 @@ impl TypeName<'_> {
         n: usize,
         type_params_fmt_opts: TypeParamsFmtOpts,
     ) -> TypeNameDisplay<'_> {
+        self.display(&TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
+
+    /// Returns an object that implements `fmt::Display` for printing the type
+    /// name with full module paths, identical to the `core::any::type_name`
+    /// string it was parsed from.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tynm::TypeName;
+    ///
+    /// let tn = TypeName::new::<Option<String>>();
+    ///
+    /// assert_eq!(
+    ///     tn.as_display_full().to_string(),
+    ///     "core::option::Option<alloc::string::String>"
+    /// );
+    /// ```
+    pub fn as_display_full(&self) -> TypeNameDisplay<'_> {
+        TypeNameDisplay {
+            inner: self,
+            fmt: TypeNameFmt::full(),
+            context: RenderContext::VERBATIM,
+        }
+    }
+
+    /// Returns an object that implements `fmt::Display` for printing the type
+    /// name using the given format options.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tynm::{TypeName, TypeNameFmt, TypeParamsFmtOpts};
+    ///
+    /// pub struct MyStruct<T>(T);
+    ///
+    /// let tn = TypeName::new::<Vec<MyStruct<String>>>();
+    /// let fmt = TypeNameFmt::new().type_params_fmt_opts(TypeParamsFmtOpts::Std);
+    ///
+    /// assert_eq!(tn.display(&fmt).to_string(), "Vec<MyStruct>");
+    /// ```
+    pub fn display(&self, fmt: &TypeNameFmt) -> TypeNameDisplay<'_> {
         TypeNameDisplay {
             inner: self,
-            segment_count_left: m,
-            segment_count_right: n,
-            type_params_fmt_opts,
+            fmt: *fmt,
+            context: RenderContext::default(),
         }
     }
 
@@ -283,160 +722,124 @@ impl TypeName<'_> {
     where
         W: Write,
     {
-        match self {
-            Self::None => Ok(()),
-            Self::Array(type_name_array) => {
-                type_name_array.write_str(buffer, m, n, type_params_fmt_opts)
-            }
-            Self::Never => buffer.write_str("!"),
-            Self::Pointer(type_name_pointer) => {
-                type_name_pointer.write_str(buffer, m, n, type_params_fmt_opts)
-            }
-            Self::Reference(type_name_reference) => {
-                type_name_reference.write_str(buffer, m, n, type_params_fmt_opts)
-            }
-            Self::Slice(type_name_slice) => {
-                type_name_slice.write_str(buffer, m, n, type_params_fmt_opts)
-            }
-            Self::Struct(type_name_struct) => {
-                type_name_struct.write_str(buffer, m, n, type_params_fmt_opts)
-            }
-            Self::Tuple(type_name_tuple) => {
-                type_name_tuple.write_str(buffer, m, n, type_params_fmt_opts)
-            }
-            Self::Trait(type_name_trait) => {
-                type_name_trait.write_str(buffer, m, n, type_params_fmt_opts)
-            }
-            Self::Unit => buffer.write_str("()"),
-        }
-    }
-}
-
-/// Type name of an array.
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct TypeNameArray<'s> {
-    /// Type of each array element.
-    pub(crate) type_param: Box<TypeName<'s>>,
-    /// Array length.
-    pub(crate) len: &'s str,
-}
-
-impl<'s> TypeNameArray<'s> {
-    /// Returns the type parameter of this type.
-    pub fn type_param(&self) -> &TypeName<'s> {
-        &self.type_param
+        self.write_str_fmt(buffer, &TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
     }
 
-    /// Returns the type parameter of this type.
-    pub fn len(&self) -> &str {
-        self.len
+    /// Returns the length in bytes of the type name string written by
+    /// `write_str`.
+    ///
+    /// # Parameters
+    ///
+    /// * `m`: Number of module segments to include, beginning from the left
+    ///   (most significant).
+    /// * `n`: Number of module segments to include, beginning from the right
+    ///   (least significant).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tynm::TypeName;
+    ///
+    /// let tn = TypeName::new::<Option<String>>();
+    ///
+    /// assert_eq!(tn.rendered_len(0, 0), "Option<String>".len());
+    /// assert_eq!(tn.rendered_len(0, 1), tn.as_str_mn(0, 1).len());
+    /// ```
+    pub fn rendered_len(&self, m: usize, n: usize) -> usize {
+        self.rendered_len_opts(m, n, TypeParamsFmtOpts::All)
     }
 
-    /// Writes the type name string to the given buffer.
-    ///
-    /// If the left and right module segments overlap, the overlapping segments
-    /// will only be printed once.
+    /// Returns the length in bytes of the type name string written by
+    /// `write_str_opts`.
     ///
     /// # Parameters
     ///
-    /// * `buffer`: Buffer to write to.
     /// * `m`: Number of module segments to include, beginning from the left
     ///   (most significant).
     /// * `n`: Number of module segments to include, beginning from the right
     ///   (least significant).
     /// * `type_params_fmt_opts`: How to format type parameters, see the type
     ///   documentation for details.
-    pub fn write_str<W>(
+    pub fn rendered_len_opts(
         &self,
-        buffer: &mut W,
         m: usize,
         n: usize,
         type_params_fmt_opts: TypeParamsFmtOpts,
-    ) -> Result<(), Error>
-    where
-        W: Write,
-    {
-        buffer.write_str("[")?;
-        self.type_param
-            .write_str_opts(buffer, m, n, type_params_fmt_opts)?;
-        buffer.write_str("; ")?;
-        buffer.write_str(self.len)?;
-        buffer.write_str("]")
+    ) -> usize {
+        self.rendered_len_with(&TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
     }
-}
 
-/// Type name of a pointer.
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct TypeNamePointer<'s> {
-    /// Type of pointer.
-    pub(crate) const_or_mut: &'s str,
-    /// Type pointed to.
-    pub(crate) type_param: Box<TypeName<'s>>,
-}
+    /// Returns the length in bytes of the type name string written by
+    /// `write_str_with`.
+    pub fn rendered_len_with(&self, fmt: &TypeNameFmt) -> usize {
+        let mut rendered_len = RenderedLen(0);
+        self.write_str_fmt(&mut rendered_len, fmt)
+            .expect("`RenderedLen` never returns an error.");
 
-impl<'s> TypeNamePointer<'s> {
-    /// Returns the `"const"` or `"mut"` str.
-    pub fn const_or_mut(&self) -> &str {
-        self.const_or_mut
+        rendered_len.0
     }
 
-    /// Returns the type parameter of this type.
-    pub fn type_param(&self) -> &TypeName<'s> {
-        &self.type_param
+    /// Writes the type name string to the given buffer using the given format
+    /// options.
+    pub fn write_str_with<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.write_str_fmt(buffer, fmt)
     }
 
-    /// Writes the type name string to the given buffer.
-    ///
-    /// If the left and right module segments overlap, the overlapping segments
-    /// will only be printed once.
-    ///
-    /// # Parameters
-    ///
-    /// * `buffer`: Buffer to write to.
-    /// * `m`: Number of module segments to include, beginning from the left
-    ///   (most significant).
-    /// * `n`: Number of module segments to include, beginning from the right
-    ///   (least significant).
-    /// * `type_params_fmt_opts`: How to format type parameters, see the type
-    ///   documentation for details.
-    pub fn write_str<W>(
+    /// Writes the type name string to the given buffer using the given format
+    /// options.
+    pub(crate) fn write_str_fmt<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        render::write_node(TreeNode::Type(self), buffer, fmt)
+    }
+
+    /// Writes the type name string to the given buffer using the given format
+    /// options and render state.
+    fn write_str_context<W>(
         &self,
         buffer: &mut W,
-        m: usize,
-        n: usize,
-        type_params_fmt_opts: TypeParamsFmtOpts,
+        fmt: &TypeNameFmt,
+        context: RenderContext,
     ) -> Result<(), Error>
     where
         W: Write,
     {
-        buffer.write_str("* ")?;
-        buffer.write_str(self.const_or_mut)?;
-        buffer.write_str(" ")?;
-        self.type_param
-            .write_str_opts(buffer, m, n, type_params_fmt_opts)
+        render::write_node_with(TreeNode::Type(self), buffer, fmt, context)
     }
 }
 
-/// Type name of a reference.
+/// Type name of an array.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct TypeNameReference<'s> {
-    /// Type of reference.
-    pub(crate) mutable: bool,
-    /// Type referenced.
+pub struct TypeNameArray<'s> {
+    /// Type of each array element.
     pub(crate) type_param: Box<TypeName<'s>>,
+    /// Array length.
+    pub(crate) len: Cow<'s, str>,
 }
 
-impl<'s> TypeNameReference<'s> {
-    /// Returns whether the reference is mutable.
-    pub fn mutable(&self) -> bool {
-        self.mutable
-    }
-
+impl<'s> TypeNameArray<'s> {
     /// Returns the type parameter of this type.
     pub fn type_param(&self) -> &TypeName<'s> {
         &self.type_param
     }
 
+    /// Returns the type parameter of this type.
+    pub fn len(&self) -> &str {
+        &self.len
+    }
+
+    /// Returns this type name with all strings owned.
+    pub fn into_owned(self) -> TypeNameArray<'static> {
+        TypeNameArray {
+            type_param: Box::new((*self.type_param).into_owned()),
+            len: cow_into_owned(self.len),
+        }
+    }
+
     /// Writes the type name string to the given buffer.
     ///
     /// If the left and right module segments overlap, the overlapping segments
@@ -461,32 +864,100 @@ impl<'s> TypeNameReference<'s> {
     where
         W: Write,
     {
-        buffer.write_str("&")?;
-        if self.mutable {
-            buffer.write_str("mut ")?;
-        }
-        self.type_param
-            .write_str_opts(buffer, m, n, type_params_fmt_opts)
+        self.write_str_fmt(buffer, &TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
+
+    /// Writes the type name string to the given buffer using the given format
+    /// options.
+    pub(crate) fn write_str_fmt<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        render::write_node(TreeNode::Array(self), buffer, fmt)
     }
 }
 
-/// Type name of a slice.
+/// Type name of a closure, `async` block, `async fn` body, or coroutine.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct TypeNameSlice<'s> {
-    /// Type of each slice element.
-    pub(crate) type_param: Box<TypeName<'s>>,
+pub struct TypeNameClosure<'s> {
+    /// Kind of closure.
+    pub(crate) kind: ClosureKind,
+    /// Leading parts of the path that are not plain segments, e.g.
+    /// `<my_crate::Foo as my_crate::Tr>::run` in
+    /// `<my_crate::Foo as my_crate::Tr>::run::{{closure}}`.
+    ///
+    /// The first part is either a qualified path or a path with generic
+    /// arguments, and later parts are paths with generic arguments, e.g.
+    /// `my_crate::Foo<u8>` and `run<u16>` in
+    /// `my_crate::Foo<u8>::run<u16>::{{closure}}`. This is empty when the whole
+    /// path is made of plain segments.
+    pub(crate) parent_path: Vec<TypeName<'s>>,
+    /// Path segments between the parent path and the closure segment.
+    pub(crate) module_path: Vec<Cow<'s, str>>,
+    /// The closure segment as it appears in the type name, e.g.
+    /// `"{{closure}}"`.
+    pub(crate) segment: Cow<'s, str>,
+    /// Path of the item that the closure is defined in, e.g. `["my_crate",
+    /// "spawn_worker"]`.
+    pub(crate) enclosing_path: Vec<Cow<'s, str>>,
 }
 
-impl<'s> TypeNameSlice<'s> {
-    /// Returns the type parameter of this type.
-    pub fn type_param(&self) -> &TypeName<'s> {
-        &self.type_param
+impl<'s> TypeNameClosure<'s> {
+    /// Returns the kind of closure.
+    pub fn kind(&self) -> ClosureKind {
+        self.kind
+    }
+
+    /// Returns the leading parts of the path that are not plain segments,
+    /// i.e. a qualified path or paths with generic arguments.
+    ///
+    /// For `my_crate::generic<u8>::{{closure}}`, this is
+    /// `my_crate::generic<u8>`.
+    pub fn parent_path(&self) -> &[TypeName<'s>] {
+        &self.parent_path
+    }
+
+    /// Returns the path segments between the parent path and the closure
+    /// segment.
+    pub fn module_path(&self) -> &[Cow<'s, str>] {
+        &self.module_path
+    }
+
+    /// Returns the closure segment as it appears in the type name, e.g.
+    /// `"{{closure}}"`.
+    pub fn segment(&self) -> &str {
+        &self.segment
+    }
+
+    /// Returns the path of the item that the closure is defined in, after the
+    /// parent path.
+    ///
+    /// This may include the segments of enclosing closures, e.g. `["my_crate",
+    /// "main", "{{closure}}"]`.
+    pub fn enclosing_path(&self) -> &[Cow<'s, str>] {
+        &self.enclosing_path
+    }
+
+    /// Returns this type name with all strings owned.
+    pub fn into_owned(self) -> TypeNameClosure<'static> {
+        TypeNameClosure {
+            kind: self.kind,
+            parent_path: self
+                .parent_path
+                .into_iter()
+                .map(TypeName::into_owned)
+                .collect(),
+            module_path: cows_into_owned(self.module_path),
+            segment: cow_into_owned(self.segment),
+            enclosing_path: cows_into_owned(self.enclosing_path),
+        }
     }
 
     /// Writes the type name string to the given buffer.
     ///
-    /// If the left and right module segments overlap, the overlapping segments
-    /// will only be printed once.
+    /// This is written in the form `{closure in spawn_worker}`, where the
+    /// module segments of the enclosing function are included according to
+    /// `m` and `n`.
     ///
     /// # Parameters
     ///
@@ -495,52 +966,84 @@ impl<'s> TypeNameSlice<'s> {
     ///   (most significant).
     /// * `n`: Number of module segments to include, beginning from the right
     ///   (least significant).
-    /// * `type_params_fmt_opts`: How to format type parameters, see the type
-    ///   documentation for details.
-    pub fn write_str<W>(
-        &self,
-        buffer: &mut W,
-        m: usize,
-        n: usize,
-        type_params_fmt_opts: TypeParamsFmtOpts,
-    ) -> Result<(), Error>
+    pub fn write_str<W>(&self, buffer: &mut W, m: usize, n: usize) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.write_str_fmt(buffer, &TypeNameFmt::mn_opts(m, n, TypeParamsFmtOpts::All))
+    }
+
+    /// Writes the type name string to the given buffer using the given format
+    /// options.
+    pub(crate) fn write_str_fmt<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
     where
         W: Write,
     {
-        // Don't need to prepend with `"&"` because slices are always passed in as
-        // references.
-        buffer.write_str("[")?;
-        self.type_param
-            .write_str_opts(buffer, m, n, type_params_fmt_opts)?;
-        buffer.write_str("]")
+        render::write_node(TreeNode::Closure(self), buffer, fmt)
     }
 }
 
-/// Type name of a struct.
+/// Type name of a function pointer, e.g. `unsafe extern "C" fn(u8) -> u8`.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct TypeNameStruct<'s> {
-    /// Module path of this type.
-    pub(crate) module_path: Vec<&'s str>,
-    /// Simple type name, excluding type parameters.
-    pub(crate) simple_name: &'s str,
-    /// Type parameters to this type.
-    pub(crate) type_params: Vec<TypeName<'s>>,
+pub struct TypeNameFunction<'s> {
+    /// Higher-ranked lifetimes, e.g. `'a` in `for<'a> fn(&'a str)`.
+    pub(crate) bound_lifetimes: Vec<Cow<'s, str>>,
+    /// Whether the function is `unsafe`.
+    pub(crate) is_unsafe: bool,
+    /// ABI of the function, e.g. `"C"` for `extern "C" fn()`.
+    pub(crate) abi: Option<Cow<'s, str>>,
+    /// Types of the function parameters.
+    pub(crate) params: Vec<TypeName<'s>>,
+    /// Whether the function is variadic, i.e. ends with `...`.
+    pub(crate) variadic: bool,
+    /// Return type of the function, `None` for `()`.
+    pub(crate) return_type: Option<Box<TypeName<'s>>>,
 }
 
-impl<'s> TypeNameStruct<'s> {
-    /// Returns the module path of the type.
-    pub fn module_path(&self) -> &[&'s str] {
-        &self.module_path
+impl<'s> TypeNameFunction<'s> {
+    /// Returns the higher-ranked lifetimes, e.g. `'a` in `for<'a> fn(&'a
+    /// str)`.
+    pub fn bound_lifetimes(&self) -> &[Cow<'s, str>] {
+        &self.bound_lifetimes
     }
 
-    /// Returns the simple name of the type, excluding type parameters.
-    pub fn simple_name(&self) -> &'s str {
-        self.simple_name
+    /// Returns whether the function is `unsafe`.
+    pub fn is_unsafe(&self) -> bool {
+        self.is_unsafe
     }
 
-    /// Returns the type parameters of this type.
-    pub fn type_params(&self) -> &[TypeName<'s>] {
-        &self.type_params
+    /// Returns the ABI of the function, e.g. `"C"` for `extern "C" fn()`.
+    pub fn abi(&self) -> Option<&str> {
+        self.abi.as_deref()
+    }
+
+    /// Returns the parameter types of the function.
+    pub fn params(&self) -> &[TypeName<'s>] {
+        &self.params
+    }
+
+    /// Returns whether the function is variadic.
+    pub fn variadic(&self) -> bool {
+        self.variadic
+    }
+
+    /// Returns the return type of the function, if it is not `()`.
+    pub fn return_type(&self) -> Option<&TypeName<'s>> {
+        self.return_type.as_deref()
+    }
+
+    /// Returns this type name with all strings owned.
+    pub fn into_owned(self) -> TypeNameFunction<'static> {
+        TypeNameFunction {
+            bound_lifetimes: cows_into_owned(self.bound_lifetimes),
+            is_unsafe: self.is_unsafe,
+            abi: self.abi.map(cow_into_owned),
+            params: type_names_into_owned(self.params),
+            variadic: self.variadic,
+            return_type: self
+                .return_type
+                .map(|return_type| Box::new((*return_type).into_owned())),
+        }
     }
 
     /// Writes the type name string to the given buffer.
@@ -567,88 +1070,48 @@ impl<'s> TypeNameStruct<'s> {
     where
         W: Write,
     {
-        let result = self
-            .write_module_path(buffer, m, n)
-            .and_then(|_| self.write_simple_name(buffer));
-
-        match type_params_fmt_opts {
-            TypeParamsFmtOpts::All => {
-                result.and_then(|_| self.write_type_params(buffer, m, n, type_params_fmt_opts))
-            }
-            TypeParamsFmtOpts::Std
-                if matches!(
-                    self.module_path.first().copied(),
-                    Some("std" | "core" | "alloc")
-                ) =>
-            {
-                result.and_then(|_| self.write_type_params(buffer, m, n, type_params_fmt_opts))
-            }
-            _ => result,
-        }
+        self.write_str_fmt(buffer, &TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
     }
 
-    /// Writes the module path to the given buffer.
-    ///
-    /// If the left and right module segments overlap, the overlapping segments
-    /// will only be printed once.
-    ///
-    /// # Parameters
-    ///
-    /// * `buffer`: Buffer to write to.
-    /// * `m`: Number of module segments to include, beginning from the left
-    ///   (most significant).
-    /// * `n`: Number of module segments to include, beginning from the right
-    ///   (least significant).
-    pub fn write_module_path<W>(&self, buffer: &mut W, m: usize, n: usize) -> Result<(), Error>
+    /// Writes the type name string to the given buffer using the given format
+    /// options.
+    pub(crate) fn write_str_fmt<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
     where
         W: Write,
     {
-        let module_segment_count = m.saturating_add(n);
-
-        if module_segment_count >= self.module_path.len() {
-            // Print full module path
-            buffer.write_str(&self.module_path.join("::"))?;
-        } else {
-            // Print leading and trailing module segments
-            buffer.write_str(&self.module_path[0..m].join("::"))?;
-
-            if m > 0 {
-                buffer.write_str("::")?;
-            }
-
-            // If we skipped any module segments, indicate this with `".."`
-            if module_segment_count > 0 {
-                buffer.write_str("..")?;
-            }
-
-            if n > 0 {
-                buffer.write_str("::")?;
-            }
+        render::write_node(TreeNode::Function(self), buffer, fmt)
+    }
+}
 
-            let len = self.module_path.len();
-            buffer.write_str(&self.module_path[(len - n)..len].join("::"))?;
-        }
+/// Type name of a pointer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeNamePointer<'s> {
+    /// Type of pointer.
+    pub(crate) const_or_mut: Cow<'s, str>,
+    /// Type pointed to.
+    pub(crate) type_param: Box<TypeName<'s>>,
+}
 
-        if module_segment_count > 0 {
-            buffer.write_str("::")?;
-        }
+impl<'s> TypeNamePointer<'s> {
+    /// Returns the `"const"` or `"mut"` str.
+    pub fn const_or_mut(&self) -> &str {
+        &self.const_or_mut
+    }
 
-        Ok(())
+    /// Returns the type parameter of this type.
+    pub fn type_param(&self) -> &TypeName<'s> {
+        &self.type_param
     }
 
-    /// Writes the simple name to the given buffer.
-    ///
-    /// # Parameters
-    ///
-    /// * `buffer`: Buffer to write to.
-    pub fn write_simple_name<W>(&self, buffer: &mut W) -> Result<(), Error>
-    where
-        W: Write,
-    {
-        buffer.write_str(self.simple_name)
+    /// Returns this type name with all strings owned.
+    pub fn into_owned(self) -> TypeNamePointer<'static> {
+        TypeNamePointer {
+            const_or_mut: cow_into_owned(self.const_or_mut),
+            type_param: Box::new((*self.type_param).into_owned()),
+        }
     }
 
-    /// Writes type parameters to the given buffer.
+    /// Writes the type name string to the given buffer.
     ///
     /// If the left and right module segments overlap, the overlapping segments
     /// will only be printed once.
@@ -660,7 +1123,9 @@ impl<'s> TypeNameStruct<'s> {
     ///   (most significant).
     /// * `n`: Number of module segments to include, beginning from the right
     ///   (least significant).
-    pub fn write_type_params<W>(
+    /// * `type_params_fmt_opts`: How to format type parameters, see the type
+    ///   documentation for details.
+    pub fn write_str<W>(
         &self,
         buffer: &mut W,
         m: usize,
@@ -670,36 +1135,54 @@ impl<'s> TypeNameStruct<'s> {
     where
         W: Write,
     {
-        if !self.type_params.is_empty() {
-            buffer.write_str("<")?;
-
-            if let Some((first, rest)) = self.type_params.split_first() {
-                first.write_str_opts(buffer, m, n, type_params_fmt_opts)?;
-                rest.iter().try_for_each(|type_param| {
-                    buffer
-                        .write_str(", ")
-                        .and_then(|_| type_param.write_str_opts(buffer, m, n, type_params_fmt_opts))
-                })?;
-            }
-
-            buffer.write_str(">")?;
-        }
+        self.write_str_fmt(buffer, &TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
 
-        Ok(())
+    /// Writes the type name string to the given buffer using the given format
+    /// options.
+    pub(crate) fn write_str_fmt<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        render::write_node(TreeNode::Pointer(self), buffer, fmt)
     }
 }
 
-/// Type name of a tuple.
+/// Type name of a qualified path, e.g. `<Vec<u8> as IntoIterator>::IntoIter`.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct TypeNameTuple<'s> {
-    /// Type parameters to this type.
-    pub(crate) type_params: Vec<TypeName<'s>>,
+pub struct TypeNameQualifiedPath<'s> {
+    /// The `Self` type, e.g. `Vec<u8>`.
+    pub(crate) self_type: Box<TypeName<'s>>,
+    /// The trait that the associated item belongs to, e.g. `IntoIterator`.
+    pub(crate) trait_path: Option<TypeNameStruct<'s>>,
+    /// Segments of the associated item path, e.g. `["IntoIter"]`.
+    pub(crate) assoc_path: Vec<Cow<'s, str>>,
 }
 
-impl<'s> TypeNameTuple<'s> {
-    /// Returns the type parameters of this type.
-    pub fn type_params(&self) -> &[TypeName<'s>] {
-        &self.type_params
+impl<'s> TypeNameQualifiedPath<'s> {
+    /// Returns the `Self` type, e.g. `Vec<u8>`.
+    pub fn self_type(&self) -> &TypeName<'s> {
+        &self.self_type
+    }
+
+    /// Returns the trait that the associated item belongs to, if specified.
+    pub fn trait_path(&self) -> Option<&TypeNameStruct<'s>> {
+        self.trait_path.as_ref()
+    }
+
+    /// Returns the segments of the associated item path, e.g.
+    /// `["IntoIter"]`.
+    pub fn assoc_path(&self) -> &[Cow<'s, str>] {
+        &self.assoc_path
+    }
+
+    /// Returns this type name with all strings owned.
+    pub fn into_owned(self) -> TypeNameQualifiedPath<'static> {
+        TypeNameQualifiedPath {
+            self_type: Box::new((*self.self_type).into_owned()),
+            trait_path: self.trait_path.map(TypeNameStruct::into_owned),
+            assoc_path: cows_into_owned(self.assoc_path),
+        }
     }
 
     /// Writes the type name string to the given buffer.
@@ -726,52 +1209,53 @@ impl<'s> TypeNameTuple<'s> {
     where
         W: Write,
     {
-        if !self.type_params.is_empty() {
-            buffer.write_str("(")?;
-
-            if let Some((first, rest)) = self.type_params.split_first() {
-                first.write_str_opts(buffer, m, n, type_params_fmt_opts)?;
-
-                if self.type_params.len() == 1 {
-                    buffer.write_str(",")?; // Always write `,` after first
-                                            // type.
-                } else {
-                    rest.iter().try_for_each(|type_param| {
-                        buffer.write_str(", ").and_then(|_| {
-                            type_param.write_str_opts(buffer, m, n, type_params_fmt_opts)
-                        })
-                    })?;
-                }
-            }
-
-            buffer.write_str(")")?;
-        }
+        self.write_str_fmt(buffer, &TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
 
-        Ok(())
+    /// Writes the type name string to the given buffer using the given format
+    /// options.
+    pub(crate) fn write_str_fmt<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        render::write_node(TreeNode::QualifiedPath(self), buffer, fmt)
     }
 }
 
-/// Type name of a trait.
+/// Type name of a reference.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct TypeNameTrait<'s> {
-    /// Share implementation with [`TypeNameStruct`]
-    pub(crate) inner: TypeNameStruct<'s>,
+pub struct TypeNameReference<'s> {
+    /// Lifetime of the reference, e.g. `'_`.
+    pub(crate) lifetime: Option<Cow<'s, str>>,
+    /// Type of reference.
+    pub(crate) mutable: bool,
+    /// Type referenced.
+    pub(crate) type_param: Box<TypeName<'s>>,
 }
 
-impl<'s> TypeNameTrait<'s> {
-    /// Returns the module path of the type.
-    pub fn module_path(&self) -> &[&'s str] {
-        &self.inner.module_path
+impl<'s> TypeNameReference<'s> {
+    /// Returns the lifetime of the reference, if present.
+    pub fn lifetime(&self) -> Option<&str> {
+        self.lifetime.as_deref()
     }
 
-    /// Returns the simple name of the type, excluding type parameters.
-    pub fn simple_name(&self) -> &'s str {
-        self.inner.simple_name
+    /// Returns whether the reference is mutable.
+    pub fn mutable(&self) -> bool {
+        self.mutable
     }
 
-    /// Returns the type parameters of this type.
-    pub fn type_params(&self) -> &[TypeName<'s>] {
-        &self.inner.type_params
+    /// Returns the type parameter of this type.
+    pub fn type_param(&self) -> &TypeName<'s> {
+        &self.type_param
+    }
+
+    /// Returns this type name with all strings owned.
+    pub fn into_owned(self) -> TypeNameReference<'static> {
+        TypeNameReference {
+            lifetime: self.lifetime.map(cow_into_owned),
+            mutable: self.mutable,
+            type_param: Box::new((*self.type_param).into_owned()),
+        }
     }
 
     /// Writes the type name string to the given buffer.
@@ -798,15 +1282,170 @@ impl<'s> TypeNameTrait<'s> {
     where
         W: Write,
     {
-        buffer.write_str("dyn ")?;
-        self.inner.write_str(buffer, m, n, type_params_fmt_opts)
+        self.write_str_fmt(buffer, &TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
     }
 
-    /// Writes the module path to the given buffer.
-    ///
-    /// If the left and right module segments overlap, the overlapping segments
-    /// will only be printed once.
-    ///
+    /// Writes the type name string to the given buffer using the given format
+    /// options.
+    pub(crate) fn write_str_fmt<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        render::write_node(TreeNode::Reference(self), buffer, fmt)
+    }
+}
+
+/// Type name of a slice.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeNameSlice<'s> {
+    /// Type of each slice element.
+    pub(crate) type_param: Box<TypeName<'s>>,
+}
+
+impl<'s> TypeNameSlice<'s> {
+    /// Returns the type parameter of this type.
+    pub fn type_param(&self) -> &TypeName<'s> {
+        &self.type_param
+    }
+
+    /// Returns this type name with all strings owned.
+    pub fn into_owned(self) -> TypeNameSlice<'static> {
+        TypeNameSlice {
+            type_param: Box::new((*self.type_param).into_owned()),
+        }
+    }
+
+    /// Writes the type name string to the given buffer.
+    ///
+    /// If the left and right module segments overlap, the overlapping segments
+    /// will only be printed once.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: Buffer to write to.
+    /// * `m`: Number of module segments to include, beginning from the left
+    ///   (most significant).
+    /// * `n`: Number of module segments to include, beginning from the right
+    ///   (least significant).
+    /// * `type_params_fmt_opts`: How to format type parameters, see the type
+    ///   documentation for details.
+    pub fn write_str<W>(
+        &self,
+        buffer: &mut W,
+        m: usize,
+        n: usize,
+        type_params_fmt_opts: TypeParamsFmtOpts,
+    ) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.write_str_fmt(buffer, &TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
+
+    /// Writes the type name string to the given buffer using the given format
+    /// options.
+    pub(crate) fn write_str_fmt<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        render::write_node(TreeNode::Slice(self), buffer, fmt)
+    }
+}
+
+/// Type name of a struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeNameStruct<'s> {
+    /// Module path of this type.
+    pub(crate) module_path: Vec<Cow<'s, str>>,
+    /// Simple type name, excluding type parameters.
+    pub(crate) simple_name: Cow<'s, str>,
+    /// Type parameters to this type.
+    pub(crate) type_params: Vec<GenericArg<'s>>,
+}
+
+impl<'s> TypeNameStruct<'s> {
+    /// Returns the module path of the type.
+    pub fn module_path(&self) -> &[Cow<'s, str>] {
+        &self.module_path
+    }
+
+    /// Returns the simple name of the type, excluding type parameters.
+    pub fn simple_name(&self) -> &str {
+        &self.simple_name
+    }
+
+    /// Returns the type parameters of this type.
+    pub fn type_params(&self) -> &[GenericArg<'s>] {
+        &self.type_params
+    }
+
+    /// Returns this type name with all strings owned.
+    pub fn into_owned(self) -> TypeNameStruct<'static> {
+        TypeNameStruct {
+            module_path: cows_into_owned(self.module_path),
+            simple_name: cow_into_owned(self.simple_name),
+            type_params: self
+                .type_params
+                .into_iter()
+                .map(GenericArg::into_owned)
+                .collect(),
+        }
+    }
+
+    /// Rewrites module paths under `core` and `alloc` to `std`, including
+    /// those of type parameters.
+    pub(crate) fn normalize(&mut self) {
+        normalize_module_path(&mut self.module_path);
+        self.type_params
+            .iter_mut()
+            .for_each(|type_param| match type_param {
+                GenericArg::Type(type_name)
+                | GenericArg::Binding(TypeNameBinding { type_name, .. }) => type_name.normalize(),
+                GenericArg::Const(_) | GenericArg::Lifetime(_) => {}
+            });
+    }
+
+    /// Writes the type name string to the given buffer.
+    ///
+    /// If the left and right module segments overlap, the overlapping segments
+    /// will only be printed once.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: Buffer to write to.
+    /// * `m`: Number of module segments to include, beginning from the left
+    ///   (most significant).
+    /// * `n`: Number of module segments to include, beginning from the right
+    ///   (least significant).
+    /// * `type_params_fmt_opts`: How to format type parameters, see the type
+    ///   documentation for details.
+    pub fn write_str<W>(
+        &self,
+        buffer: &mut W,
+        m: usize,
+        n: usize,
+        type_params_fmt_opts: TypeParamsFmtOpts,
+    ) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.write_str_fmt(buffer, &TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
+
+    /// Writes the type name string to the given buffer using the given format
+    /// options.
+    pub(crate) fn write_str_fmt<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        render::write_node(TreeNode::Struct(self), buffer, fmt)
+    }
+
+    /// Writes the module path to the given buffer.
+    ///
+    /// If the left and right module segments overlap, the overlapping segments
+    /// will only be printed once.
+    ///
     /// # Parameters
     ///
     /// * `buffer`: Buffer to write to.
@@ -818,74 +1457,715 @@ impl<'s> TypeNameTrait<'s> {
     where
         W: Write,
     {
-        self.inner.write_module_path(buffer, m, n)
+        stream::write_module_path(
+            buffer,
+            self.module_path.iter().map(|segment| segment.as_ref()),
+            self.module_path.len(),
+            &TypeNameFmt::mn_opts(m, n, TypeParamsFmtOpts::All),
+        )
+    }
+
+    /// Writes the simple name to the given buffer.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: Buffer to write to.
+    pub fn write_simple_name<W>(&self, buffer: &mut W) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        buffer.write_str(&self.simple_name)
+    }
+
+    /// Writes type parameters to the given buffer.
+    ///
+    /// If the left and right module segments overlap, the overlapping segments
+    /// will only be printed once.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: Buffer to write to.
+    /// * `m`: Number of module segments to include, beginning from the left
+    ///   (most significant).
+    /// * `n`: Number of module segments to include, beginning from the right
+    ///   (least significant).
+    pub fn write_type_params<W>(
+        &self,
+        buffer: &mut W,
+        m: usize,
+        n: usize,
+        type_params_fmt_opts: TypeParamsFmtOpts,
+    ) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.write_type_params_fmt(buffer, &TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
+
+    /// Writes type parameters to the given buffer using the given format
+    /// options.
+    pub(crate) fn write_type_params_fmt<W>(
+        &self,
+        buffer: &mut W,
+        fmt: &TypeNameFmt,
+    ) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        let type_params = TreeNodes::GenericArgs(self.type_params.iter());
+        render::write_angle_type_params::<TreeNode<'_, '_>, _>(
+            buffer,
+            type_params,
+            fmt,
+            RenderContext::default(),
+        )
+    }
+}
+
+/// Generic argument of a type, e.g. `u8` or `Item = u8` in `Foo<u8>` or `dyn
+/// Iterator<Item = u8>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GenericArg<'s> {
+    /// Type argument, e.g. `u8` in `Vec<u8>`.
+    Type(TypeName<'s>),
+    /// Const argument, e.g. `16` in `ArrayVec<u8, 16>`.
+    Const(Cow<'s, str>),
+    /// Lifetime argument, e.g. `'_` in `Cow<'_, str>`.
+    Lifetime(Cow<'s, str>),
+    /// Associated type binding, e.g. `Item = u8` in `dyn Iterator<Item = u8>`.
+    Binding(TypeNameBinding<'s>),
+}
+
+impl GenericArg<'_> {
+    /// Returns this generic argument with all strings owned.
+    pub fn into_owned(self) -> GenericArg<'static> {
+        match self {
+            Self::Type(type_name) => GenericArg::Type(type_name.into_owned()),
+            Self::Const(value) => GenericArg::Const(cow_into_owned(value)),
+            Self::Lifetime(lifetime) => GenericArg::Lifetime(cow_into_owned(lifetime)),
+            Self::Binding(type_name_binding) => GenericArg::Binding(type_name_binding.into_owned()),
+        }
+    }
+
+    /// Writes the generic argument to the given buffer.
+    ///
+    /// If the left and right module segments overlap, the overlapping segments
+    /// will only be printed once.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: Buffer to write to.
+    /// * `m`: Number of module segments to include, beginning from the left
+    ///   (most significant).
+    /// * `n`: Number of module segments to include, beginning from the right
+    ///   (least significant).
+    /// * `type_params_fmt_opts`: How to format type parameters, see the type
+    ///   documentation for details.
+    pub fn write_str<W>(
+        &self,
+        buffer: &mut W,
+        m: usize,
+        n: usize,
+        type_params_fmt_opts: TypeParamsFmtOpts,
+    ) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.write_str_fmt(buffer, &TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
+
+    /// Writes the generic argument to the given buffer using the given format
+    /// options.
+    pub(crate) fn write_str_fmt<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        render::write_node(TreeNode::GenericArg(self), buffer, fmt)
+    }
+}
+
+/// Associated type binding, e.g. `Item = u8` in `dyn Iterator<Item = u8>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeNameBinding<'s> {
+    /// Name of the associated type, e.g. `Item`.
+    pub(crate) name: Cow<'s, str>,
+    /// Type bound to the associated type, e.g. `u8`.
+    pub(crate) type_name: TypeName<'s>,
+}
+
+impl<'s> TypeNameBinding<'s> {
+    /// Returns the name of the associated type, e.g. `Item`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type bound to the associated type.
+    pub fn type_name(&self) -> &TypeName<'s> {
+        &self.type_name
+    }
+
+    /// Returns this binding with all strings owned.
+    pub fn into_owned(self) -> TypeNameBinding<'static> {
+        TypeNameBinding {
+            name: cow_into_owned(self.name),
+            type_name: self.type_name.into_owned(),
+        }
+    }
+}
+
+/// Type name of a tuple.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeNameTuple<'s> {
+    /// Type parameters to this type.
+    pub(crate) type_params: Vec<TypeName<'s>>,
+}
+
+impl<'s> TypeNameTuple<'s> {
+    /// Returns the type parameters of this type.
+    pub fn type_params(&self) -> &[TypeName<'s>] {
+        &self.type_params
+    }
+
+    /// Returns this type name with all strings owned.
+    pub fn into_owned(self) -> TypeNameTuple<'static> {
+        TypeNameTuple {
+            type_params: type_names_into_owned(self.type_params),
+        }
+    }
+
+    /// Writes the type name string to the given buffer.
+    ///
+    /// If the left and right module segments overlap, the overlapping segments
+    /// will only be printed once.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: Buffer to write to.
+    /// * `m`: Number of module segments to include, beginning from the left
+    ///   (most significant).
+    /// * `n`: Number of module segments to include, beginning from the right
+    ///   (least significant).
+    /// * `type_params_fmt_opts`: How to format type parameters, see the type
+    ///   documentation for details.
+    pub fn write_str<W>(
+        &self,
+        buffer: &mut W,
+        m: usize,
+        n: usize,
+        type_params_fmt_opts: TypeParamsFmtOpts,
+    ) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.write_str_fmt(buffer, &TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
+
+    /// Writes the type name string to the given buffer using the given format
+    /// options.
+    pub(crate) fn write_str_fmt<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        render::write_node(TreeNode::Tuple(self), buffer, fmt)
+    }
+}
+
+/// Type name of a trait object, e.g. `dyn Error + Send + Sync`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeNameTrait<'s> {
+    /// Trait bounds, the first of which is the principal trait.
+    pub(crate) bounds: Vec<TypeNameTraitBound<'s>>,
+    /// Lifetime bounds, e.g. `'static`.
+    pub(crate) lifetime_bounds: Vec<Cow<'s, str>>,
+    /// Whether the trait object is surrounded by parentheses, e.g. `&(dyn A +
+    /// Send)`.
+    pub(crate) parenthesized: bool,
+}
+
+impl<'s> TypeNameTrait<'s> {
+    /// Returns the trait bounds, the first of which is the principal trait.
+    pub fn bounds(&self) -> &[TypeNameTraitBound<'s>] {
+        &self.bounds
+    }
+
+    /// Returns the lifetime bounds, e.g. `'static`.
+    pub fn lifetime_bounds(&self) -> &[Cow<'s, str>] {
+        &self.lifetime_bounds
+    }
+
+    /// Returns whether the trait object is surrounded by parentheses.
+    pub fn parenthesized(&self) -> bool {
+        self.parenthesized
+    }
+
+    /// Returns the module path of the principal trait.
+    ///
+    /// This is empty if there is no principal trait.
+    pub fn module_path(&self) -> &[Cow<'s, str>] {
+        self.principal()
+            .map_or(&[], |principal| &principal.inner.module_path)
+    }
+
+    /// Returns the simple name of the principal trait, excluding type
+    /// parameters.
+    ///
+    /// This is empty if there is no principal trait.
+    pub fn simple_name(&self) -> &str {
+        self.principal()
+            .map_or("", |principal| &principal.inner.simple_name)
+    }
+
+    /// Returns the type parameters of the principal trait.
+    ///
+    /// This is empty if there is no principal trait.
+    pub fn type_params(&self) -> &[GenericArg<'s>] {
+        self.principal()
+            .map_or(&[], |principal| &principal.inner.type_params)
+    }
+
+    /// Returns the principal trait, which is the first trait bound.
+    ///
+    /// This is `None` for a trait object without trait bounds, which the
+    /// parser does not build.
+    fn principal(&self) -> Option<&TypeNameTraitBound<'s>> {
+        self.bounds.first()
+    }
+
+    /// Returns this type name with all strings owned.
+    pub fn into_owned(self) -> TypeNameTrait<'static> {
+        TypeNameTrait {
+            bounds: self
+                .bounds
+                .into_iter()
+                .map(TypeNameTraitBound::into_owned)
+                .collect(),
+            lifetime_bounds: cows_into_owned(self.lifetime_bounds),
+            parenthesized: self.parenthesized,
+        }
+    }
+
+    /// Writes the type name string to the given buffer.
+    ///
+    /// If the left and right module segments overlap, the overlapping segments
+    /// will only be printed once.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: Buffer to write to.
+    /// * `m`: Number of module segments to include, beginning from the left
+    ///   (most significant).
+    /// * `n`: Number of module segments to include, beginning from the right
+    ///   (least significant).
+    /// * `type_params_fmt_opts`: How to format type parameters, see the type
+    ///   documentation for details.
+    pub fn write_str<W>(
+        &self,
+        buffer: &mut W,
+        m: usize,
+        n: usize,
+        type_params_fmt_opts: TypeParamsFmtOpts,
+    ) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.write_str_fmt(buffer, &TypeNameFmt::mn_opts(m, n, type_params_fmt_opts))
+    }
+
+    /// Writes the type name string to the given buffer using the given format
+    /// options.
+    pub(crate) fn write_str_fmt<W>(&self, buffer: &mut W, fmt: &TypeNameFmt) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        render::write_node(TreeNode::Trait(self), buffer, fmt)
+    }
+
+    /// Writes the module path of the principal trait to the given buffer.
+    ///
+    /// If the left and right module segments overlap, the overlapping segments
+    /// will only be printed once.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: Buffer to write to.
+    /// * `m`: Number of module segments to include, beginning from the left
+    ///   (most significant).
+    /// * `n`: Number of module segments to include, beginning from the right
+    ///   (least significant).
+    pub fn write_module_path<W>(&self, buffer: &mut W, m: usize, n: usize) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.principal().map_or(Ok(()), |principal| {
+            principal.inner.write_module_path(buffer, m, n)
+        })
+    }
+
+    /// Writes the simple name of the principal trait to the given buffer.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: Buffer to write to.
+    pub fn write_simple_name<W>(&self, buffer: &mut W) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.principal().map_or(Ok(()), |principal| {
+            principal.inner.write_simple_name(buffer)
+        })
+    }
+
+    /// Writes type parameters of the principal trait to the given buffer.
+    ///
+    /// If the left and right module segments overlap, the overlapping segments
+    /// will only be printed once.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: Buffer to write to.
+    /// * `m`: Number of module segments to include, beginning from the left
+    ///   (most significant).
+    /// * `n`: Number of module segments to include, beginning from the right
+    ///   (least significant).
+    pub fn write_type_params<W>(
+        &self,
+        buffer: &mut W,
+        m: usize,
+        n: usize,
+        type_params_fmt_opts: TypeParamsFmtOpts,
+    ) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        self.principal().map_or(Ok(()), |principal| {
+            principal
+                .inner
+                .write_type_params(buffer, m, n, type_params_fmt_opts)
+        })
+    }
+}
+
+/// Trait bound of a trait object, e.g. `Debug` or `for<'a> Fn(&'a str) ->
+/// u32`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeNameTraitBound<'s> {
+    /// Higher-ranked lifetimes, e.g. `'a` in `for<'a> Fn(&'a str)`.
+    pub(crate) bound_lifetimes: Vec<Cow<'s, str>>,
+    /// Path and angle bracketed type parameters of the trait.
+    pub(crate) inner: TypeNameStruct<'s>,
+    /// Parameters of `Fn`-like traits, e.g. `&str` in `Fn(&str) -> u32`.
+    pub(crate) fn_params: Option<Vec<TypeName<'s>>>,
+    /// Return type of `Fn`-like traits, e.g. `u32` in `Fn(&str) -> u32`.
+    pub(crate) fn_return_type: Option<Box<TypeName<'s>>>,
+}
+
+impl<'s> TypeNameTraitBound<'s> {
+    /// Returns the higher-ranked lifetimes, e.g. `'a` in `for<'a> Fn(&'a
+    /// str)`.
+    pub fn bound_lifetimes(&self) -> &[Cow<'s, str>] {
+        &self.bound_lifetimes
+    }
+
+    /// Returns the module path of the trait.
+    pub fn module_path(&self) -> &[Cow<'s, str>] {
+        &self.inner.module_path
+    }
+
+    /// Returns the simple name of the trait, excluding type parameters.
+    pub fn simple_name(&self) -> &str {
+        &self.inner.simple_name
+    }
+
+    /// Returns the angle bracketed type parameters of the trait.
+    pub fn type_params(&self) -> &[GenericArg<'s>] {
+        &self.inner.type_params
+    }
+
+    /// Returns the parameters of `Fn`-like traits, e.g. `&str` in `Fn(&str) ->
+    /// u32`.
+    ///
+    /// This is `None` if the trait is not written in the parenthesized form.
+    pub fn fn_params(&self) -> Option<&[TypeName<'s>]> {
+        self.fn_params.as_deref()
+    }
+
+    /// Returns the return type of `Fn`-like traits, e.g. `u32` in `Fn(&str) ->
+    /// u32`.
+    pub fn fn_return_type(&self) -> Option<&TypeName<'s>> {
+        self.fn_return_type.as_deref()
+    }
+
+    /// Returns this trait bound with all strings owned.
+    pub fn into_owned(self) -> TypeNameTraitBound<'static> {
+        TypeNameTraitBound {
+            bound_lifetimes: cows_into_owned(self.bound_lifetimes),
+            inner: self.inner.into_owned(),
+            fn_params: self.fn_params.map(type_names_into_owned),
+            fn_return_type: self
+                .fn_return_type
+                .map(|fn_return_type| Box::new((*fn_return_type).into_owned())),
+        }
+    }
+
+    /// Rewrites module paths under `core` and `alloc` to `std`, including
+    /// those of type parameters.
+    pub(crate) fn normalize(&mut self) {
+        self.inner.normalize();
+        if let Some(fn_params) = self.fn_params.as_mut() {
+            fn_params.iter_mut().for_each(TypeName::normalize);
+        }
+        if let Some(fn_return_type) = self.fn_return_type.as_deref_mut() {
+            fn_return_type.normalize();
+        }
+    }
+}
+
+/// Reference to a node of a `TypeName` tree, which is rendered by
+/// [`render::write_node`].
+#[derive(Clone, Copy)]
+pub(crate) enum TreeNode<'a, 's> {
+    Type(&'a TypeName<'s>),
+    Array(&'a TypeNameArray<'s>),
+    Closure(&'a TypeNameClosure<'s>),
+    Function(&'a TypeNameFunction<'s>),
+    Pointer(&'a TypeNamePointer<'s>),
+    QualifiedPath(&'a TypeNameQualifiedPath<'s>),
+    Reference(&'a TypeNameReference<'s>),
+    Slice(&'a TypeNameSlice<'s>),
+    Struct(&'a TypeNameStruct<'s>),
+    GenericArg(&'a GenericArg<'s>),
+    Binding(&'a TypeNameBinding<'s>),
+    Tuple(&'a TypeNameTuple<'s>),
+    Trait(&'a TypeNameTrait<'s>),
+    TraitBound(&'a TypeNameTraitBound<'s>),
+}
+
+impl<'a, 's> RenderNode<'a> for TreeNode<'a, 's> {
+    type Nodes = TreeNodes<'a, 's>;
+    type Segments = TreeSegments<'a, 's>;
+
+    fn view(self) -> NodeView<'a, Self> {
+        let segments = |segments: &'a [Cow<'s, str>]| TreeSegments(segments.iter());
+        let type_names = |type_names: &'a [TypeName<'s>]| TreeNodes::Types(type_names.iter());
+
+        match self {
+            Self::Type(type_name) => match type_name {
+                TypeName::None => NodeView::None,
+                TypeName::Array(type_name_array) => Self::Array(type_name_array).view(),
+                TypeName::Closure(type_name_closure) => Self::Closure(type_name_closure).view(),
+                TypeName::Function(type_name_function) => Self::Function(type_name_function).view(),
+                TypeName::Never => NodeView::Never,
+                TypeName::Pointer(type_name_pointer) => Self::Pointer(type_name_pointer).view(),
+                TypeName::QualifiedPath(type_name_qualified_path) => {
+                    Self::QualifiedPath(type_name_qualified_path).view()
+                }
+                TypeName::Reference(type_name_reference) => {
+                    Self::Reference(type_name_reference).view()
+                }
+                TypeName::Slice(type_name_slice) => Self::Slice(type_name_slice).view(),
+                TypeName::Struct(type_name_struct) => Self::Struct(type_name_struct).view(),
+                TypeName::Tuple(type_name_tuple) => Self::Tuple(type_name_tuple).view(),
+                TypeName::Trait(type_name_trait) => Self::Trait(type_name_trait).view(),
+                TypeName::Unit => NodeView::Unit,
+                TypeName::Unknown(text) => NodeView::Unknown(text),
+                TypeName::Elided(text) => NodeView::Elided(text),
+            },
+            Self::Array(type_name_array) => NodeView::Array {
+                type_param: Self::Type(&type_name_array.type_param),
+                len: &type_name_array.len,
+            },
+            Self::Closure(type_name_closure) => NodeView::Closure {
+                kind: type_name_closure.kind,
+                parent_path: type_names(&type_name_closure.parent_path),
+                module_path: segments(&type_name_closure.module_path),
+                segment: &type_name_closure.segment,
+                enclosing_path: segments(&type_name_closure.enclosing_path),
+            },
+            Self::Function(type_name_function) => NodeView::Function {
+                bound_lifetimes: segments(&type_name_function.bound_lifetimes),
+                is_unsafe: type_name_function.is_unsafe,
+                abi: type_name_function.abi.as_deref(),
+                params: type_names(&type_name_function.params),
+                variadic: type_name_function.variadic,
+                return_type: type_name_function.return_type.as_deref().map(Self::Type),
+            },
+            Self::Pointer(type_name_pointer) => NodeView::Pointer {
+                const_or_mut: &type_name_pointer.const_or_mut,
+                type_param: Self::Type(&type_name_pointer.type_param),
+            },
+            Self::QualifiedPath(type_name_qualified_path) => NodeView::QualifiedPath {
+                self_type: Self::Type(&type_name_qualified_path.self_type),
+                trait_path: type_name_qualified_path
+                    .trait_path
+                    .as_ref()
+                    .map(Self::Struct),
+                assoc_path: segments(&type_name_qualified_path.assoc_path),
+            },
+            Self::Reference(type_name_reference) => NodeView::Reference {
+                lifetime: type_name_reference.lifetime.as_deref(),
+                mutable: type_name_reference.mutable,
+                type_param: Self::Type(&type_name_reference.type_param),
+            },
+            Self::Slice(type_name_slice) => NodeView::Slice {
+                type_param: Self::Type(&type_name_slice.type_param),
+            },
+            Self::Struct(type_name_struct) => NodeView::Struct {
+                module_path: segments(&type_name_struct.module_path),
+                simple_name: &type_name_struct.simple_name,
+                type_params: TreeNodes::GenericArgs(type_name_struct.type_params.iter()),
+            },
+            Self::GenericArg(generic_arg) => match generic_arg {
+                GenericArg::Type(type_name) => Self::Type(type_name).view(),
+                GenericArg::Const(value) => NodeView::Const(value),
+                GenericArg::Lifetime(lifetime) => NodeView::Lifetime(lifetime),
+                GenericArg::Binding(type_name_binding) => Self::Binding(type_name_binding).view(),
+            },
+            Self::Binding(type_name_binding) => NodeView::Binding {
+                name: &type_name_binding.name,
+                type_name: Self::Type(&type_name_binding.type_name),
+            },
+            Self::Tuple(type_name_tuple) => NodeView::Tuple {
+                type_params: type_names(&type_name_tuple.type_params),
+            },
+            Self::Trait(type_name_trait) => NodeView::Trait {
+                bounds: TreeNodes::TraitBounds(type_name_trait.bounds.iter()),
+                lifetime_bounds: segments(&type_name_trait.lifetime_bounds),
+                parenthesized: type_name_trait.parenthesized,
+            },
+            Self::TraitBound(type_name_trait_bound) => NodeView::TraitBound {
+                bound_lifetimes: segments(&type_name_trait_bound.bound_lifetimes),
+                inner: Self::Struct(&type_name_trait_bound.inner),
+                fn_params: type_name_trait_bound.fn_params.as_deref().map(type_names),
+                fn_return_type: type_name_trait_bound
+                    .fn_return_type
+                    .as_deref()
+                    .map(Self::Type),
+            },
+        }
+    }
+}
+
+/// Iterator over the nodes of a list in a `TypeName` tree.
+#[derive(Clone)]
+pub(crate) enum TreeNodes<'a, 's> {
+    Types(core::slice::Iter<'a, TypeName<'s>>),
+    GenericArgs(core::slice::Iter<'a, GenericArg<'s>>),
+    TraitBounds(core::slice::Iter<'a, TypeNameTraitBound<'s>>),
+}
+
+impl<'a, 's> Iterator for TreeNodes<'a, 's> {
+    type Item = TreeNode<'a, 's>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Types(type_names) => type_names.next().map(TreeNode::Type),
+            Self::GenericArgs(generic_args) => generic_args.next().map(TreeNode::GenericArg),
+            Self::TraitBounds(bounds) => bounds.next().map(TreeNode::TraitBound),
+        }
     }
 
-    /// Writes the simple name to the given buffer.
-    ///
-    /// # Parameters
-    ///
-    /// * `buffer`: Buffer to write to.
-    pub fn write_simple_name<W>(&self, buffer: &mut W) -> Result<(), Error>
-    where
-        W: Write,
-    {
-        self.inner.write_simple_name(buffer)
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Types(type_names) => type_names.size_hint(),
+            Self::GenericArgs(generic_args) => generic_args.size_hint(),
+            Self::TraitBounds(bounds) => bounds.size_hint(),
+        }
     }
+}
 
-    /// Writes type parameters to the given buffer.
-    ///
-    /// If the left and right module segments overlap, the overlapping segments
-    /// will only be printed once.
-    ///
-    /// # Parameters
-    ///
-    /// * `buffer`: Buffer to write to.
-    /// * `m`: Number of module segments to include, beginning from the left
-    ///   (most significant).
-    /// * `n`: Number of module segments to include, beginning from the right
-    ///   (least significant).
-    pub fn write_type_params<W>(
-        &self,
-        buffer: &mut W,
-        m: usize,
-        n: usize,
-        type_params_fmt_opts: TypeParamsFmtOpts,
-    ) -> Result<(), Error>
-    where
-        W: Write,
+impl ExactSizeIterator for TreeNodes<'_, '_> {}
+
+/// Iterator over path segments or lifetimes in a `TypeName` tree.
+#[derive(Clone)]
+pub(crate) struct TreeSegments<'a, 's>(core::slice::Iter<'a, Cow<'s, str>>);
+
+impl<'a> Iterator for TreeSegments<'a, '_> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Cow::as_ref)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for TreeSegments<'_, '_> {}
+
+/// Replaces a leading `core` or `alloc` segment of the module path with `std`.
+fn normalize_module_path(module_path: &mut [Cow<'_, str>]) {
+    if let Some(crate_name) = module_path.first_mut()
+        && matches!(crate_name.as_ref(), "core" | "alloc")
     {
-        self.inner
-            .write_type_params(buffer, m, n, type_params_fmt_opts)
+        *crate_name = Cow::Borrowed("std");
+    }
+}
+
+/// `fmt::Write` implementation that only counts the number of bytes written.
+struct RenderedLen(usize);
+
+impl Write for RenderedLen {
+    fn write_str(&mut self, s: &str) -> Result<(), Error> {
+        self.0 += s.len();
+        Ok(())
     }
 }
 
+/// Returns an owned copy of the string, which does not borrow the parsed input.
+pub(crate) fn cow_into_owned(s: Cow<'_, str>) -> Cow<'static, str> {
+    Cow::Owned(s.into_owned())
+}
+
+/// Returns owned copies of the strings, which do not borrow the parsed input.
+fn cows_into_owned(strs: Vec<Cow<'_, str>>) -> Vec<Cow<'static, str>> {
+    strs.into_iter().map(cow_into_owned).collect()
+}
+
+/// Returns the type names with all strings owned.
+fn type_names_into_owned(type_names: Vec<TypeName<'_>>) -> Vec<TypeName<'static>> {
+    type_names.into_iter().map(TypeName::into_owned).collect()
+}
+
+/// Parses a type name string.
+///
+/// If the string cannot be fully parsed, it is captured whole as
+/// [`TypeName::Unknown`].
 impl<'s> From<&'s str> for TypeName<'s> {
     fn from(std_type_name: &'s str) -> Self {
-        parser::type_name(std_type_name)
-            .map(|(_input, type_name)| type_name)
-            .unwrap_or_else(|e| {
-                panic!(
-                    "Failed to parse `TypeName` for input string: `{}`. Error: `{:?}`.",
-                    std_type_name, e,
-                )
-            })
+        Self::parse_strict(std_type_name).unwrap_or(TypeName::Unknown(Cow::Borrowed(std_type_name)))
+    }
+}
+
+/// Parses a type name string, requiring the whole string to be consumed.
+///
+/// The returned `TypeName` owns its strings, see [`TypeName::into_owned`].
+impl FromStr for TypeName<'static> {
+    type Err = ParseError;
+
+    fn from_str(std_type_name: &str) -> Result<Self, Self::Err> {
+        TypeName::parse_strict(std_type_name).map(TypeName::into_owned)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use alloc::{vec, vec::Vec};
+    use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
 
     use pretty_assertions::assert_eq;
 
-    use super::{TypeName, TypeNameStruct};
+    use crate::{ParseError, ParseErrorKind, ParseLimits, TypeNameFmt, TypeParamsFmtOpts};
+
+    use super::{
+        ClosureKind, GenericArg, TypeName, TypeNameBinding, TypeNameClosure, TypeNameQualifiedPath,
+        TypeNameReference, TypeNameStruct, TypeNameTrait, TypeNameTraitBound, TypeNameTuple,
+    };
 
     macro_rules! type_name_simple {
         () => {{
             TypeName::Struct(TypeNameStruct {
-                module_path: vec!["tynm", "types", "tests"],
-                simple_name: "Simple",
+                module_path: vec!["tynm".into(), "types".into(), "tests".into()],
+                simple_name: "Simple".into(),
                 type_params: Vec::new(),
             })
         }};
@@ -893,9 +2173,9 @@ mod tests {
     macro_rules! type_name_type_param_single {
         () => {{
             TypeName::Struct(TypeNameStruct {
-                module_path: vec!["tynm", "types", "tests"],
-                simple_name: "TypeParamSingle",
-                type_params: vec![type_name_simple!()],
+                module_path: vec!["tynm".into(), "types".into(), "tests".into()],
+                simple_name: "TypeParamSingle".into(),
+                type_params: vec![GenericArg::Type(type_name_simple!())],
             })
         }};
     }
@@ -909,6 +2189,29 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn write_struct_module_path() {
+        let type_name_struct = TypeNameStruct {
+            module_path: vec!["my_crate".into(), "a".into(), "b".into()],
+            simple_name: "Foo".into(),
+            type_params: vec![],
+        };
+        let module_path = |m, n| {
+            let mut buffer = String::new();
+            type_name_struct
+                .write_module_path(&mut buffer, m, n)
+                .expect("Writing to a `String` never fails.");
+            buffer
+        };
+
+        assert_eq!("", module_path(0, 0));
+        assert_eq!("my_crate::..::", module_path(1, 0));
+        assert_eq!("..::b::", module_path(0, 1));
+        assert_eq!("my_crate::..::b::", module_path(1, 1));
+        assert_eq!("my_crate::a::b::", module_path(2, 2));
+        assert_eq!("my_crate::a::b::", module_path(usize::MAX, usize::MAX));
+    }
+
     #[test]
     fn parse_type_parameterized_struct() {
         let expected = type_name_type_param_single!();
@@ -921,9 +2224,9 @@ mod tests {
     #[test]
     fn parse_nested_type_parameterized_struct() {
         let expected = TypeName::Struct(TypeNameStruct {
-            module_path: vec!["tynm", "types", "tests"],
-            simple_name: "TypeParamSingle",
-            type_params: vec![type_name_type_param_single!()],
+            module_path: vec!["tynm".into(), "types".into(), "tests".into()],
+            simple_name: "TypeParamSingle".into(),
+            type_params: vec![GenericArg::Type(type_name_type_param_single!())],
         });
 
         let actual = TypeName::from(core::any::type_name::<
@@ -936,9 +2239,12 @@ mod tests {
     #[test]
     fn parse_multi_type_parameterized_struct() {
         let expected = TypeName::Struct(TypeNameStruct {
-            module_path: vec!["tynm", "types", "tests"],
-            simple_name: "TypeParamDouble",
-            type_params: vec![type_name_simple!(), type_name_simple!()],
+            module_path: vec!["tynm".into(), "types".into(), "tests".into()],
+            simple_name: "TypeParamDouble".into(),
+            type_params: vec![
+                GenericArg::Type(type_name_simple!()),
+                GenericArg::Type(type_name_simple!()),
+            ],
         });
 
         let actual = TypeName::from(core::any::type_name::<TypeParamDouble<Simple, Simple>>());
@@ -949,11 +2255,11 @@ mod tests {
     #[test]
     fn parse_nested_multi_type_parameterized_struct() {
         let expected = TypeName::Struct(TypeNameStruct {
-            module_path: vec!["tynm", "types", "tests"],
-            simple_name: "TypeParamDouble",
+            module_path: vec!["tynm".into(), "types".into(), "tests".into()],
+            simple_name: "TypeParamDouble".into(),
             type_params: vec![
-                type_name_type_param_single!(),
-                type_name_type_param_single!(),
+                GenericArg::Type(type_name_type_param_single!()),
+                GenericArg::Type(type_name_type_param_single!()),
             ],
         });
 
@@ -964,7 +2270,712 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn parse_closure() {
+        let expected = TypeName::Closure(TypeNameClosure {
+            kind: ClosureKind::Closure,
+            parent_path: vec![],
+            module_path: vec!["my_crate".into(), "handlers".into(), "spawn_worker".into()],
+            segment: "{{closure}}".into(),
+            enclosing_path: vec!["my_crate".into(), "handlers".into(), "spawn_worker".into()],
+        });
+
+        let actual = TypeName::from("my_crate::handlers::spawn_worker::{{closure}}");
+
+        assert_eq!(expected, actual);
+        assert_eq!("{closure in spawn_worker}", actual.as_str());
+        assert_eq!(
+            "{closure in my_crate::..::spawn_worker}",
+            actual.as_str_mn(1, 0)
+        );
+    }
+
+    #[test]
+    fn parse_closure_disambiguated() {
+        let type_name = TypeName::from("my_crate::main::{closure#0}::{closure#1}");
+
+        assert_eq!("{closure in main}", type_name.as_str());
+        assert_eq!("{closure in my_crate::main}", type_name.as_str_mn(0, 1));
+    }
+
+    #[test]
+    fn parse_async_fn_body() {
+        let expected = TypeName::Closure(TypeNameClosure {
+            kind: ClosureKind::AsyncFnBody,
+            parent_path: vec![],
+            module_path: vec![],
+            segment: "{async fn body of my_crate::run()}".into(),
+            enclosing_path: vec!["my_crate".into(), "run".into()],
+        });
+
+        let actual = TypeName::from("{async fn body of my_crate::run()}");
+
+        assert_eq!(expected, actual);
+        assert_eq!("{async fn run}", actual.as_str());
+        assert_eq!("{async fn my_crate::run}", actual.as_str_mn(1, 0));
+    }
+
+    #[test]
+    fn parse_async_block_and_coroutine() {
+        let async_block = TypeName::from("my_crate::main::{async_block#0}");
+        let coroutine = TypeName::from("my_crate::main::{coroutine#0}");
+        let async_block_located = TypeName::from("{async block@src/main.rs:3:5}");
+
+        assert_eq!("{async block in main}", async_block.as_str());
+        assert_eq!("{coroutine in main}", coroutine.as_str());
+        assert_eq!("{async block}", async_block_located.as_str());
+    }
+
+    #[test]
+    fn parse_async_block_from_type_name() {
+        let async_block = async {};
+        let type_name = TypeName::parse_strict(core::any::type_name_of_val(&async_block))
+            .expect("Expected `async` block type name to parse.");
+
+        let TypeName::Closure(type_name_closure) = &type_name else {
+            panic!("Expected a closure type name, but got: {type_name:?}");
+        };
+        assert_eq!(ClosureKind::Closure, type_name_closure.kind());
+        assert_eq!("{{closure}}", type_name_closure.segment());
+        assert_eq!(
+            "{closure in parse_async_block_from_type_name}",
+            type_name.as_str()
+        );
+    }
+
+    #[test]
+    fn parse_closure_in_generic_fn() {
+        let type_name_qualified = closure_in_generic_fn(1u8);
+        let type_name = TypeName::parse_strict(type_name_qualified)
+            .expect("Expected closure type name to parse.");
+
+        let TypeName::Closure(type_name_closure) = &type_name else {
+            panic!("Expected a closure type name, but got: {type_name:?}");
+        };
+        assert_eq!(
+            ["tynm::types::tests::closure_in_generic_fn<u8>"],
+            type_name_closure
+                .parent_path()
+                .iter()
+                .map(TypeName::to_full_string)
+                .collect::<Vec<_>>()
+                .as_slice()
+        );
+        assert_eq!(type_name_qualified, type_name.to_full_string());
+        assert_eq!("{closure in closure_in_generic_fn}", type_name.as_str());
+        assert_eq!(
+            "{closure in tynm::..::closure_in_generic_fn}",
+            type_name.as_str_mn(1, 0)
+        );
+    }
+
+    #[test]
+    fn parse_closure_in_method_of_generic_type() {
+        let type_name_qualified = TypeParamSingle::<u8>::closure_in_method(1u16);
+        let type_name = TypeName::parse_strict(type_name_qualified)
+            .expect("Expected closure type name to parse.");
+
+        assert_eq!(type_name_qualified, type_name.to_full_string());
+        assert_eq!("{closure in closure_in_method}", type_name.as_str());
+        assert_eq!(
+            "{closure in ..::TypeParamSingle::closure_in_method}",
+            type_name.as_str_mn(0, 1)
+        );
+    }
+
+    #[test]
+    fn parse_closure_in_trait_impl_method() {
+        let type_name_qualified =
+            <TypeParamSingle<Simple> as ClosureInTraitImpl>::closure_in_trait_method();
+        let type_name = TypeName::parse_strict(type_name_qualified)
+            .expect("Expected closure type name to parse.");
+
+        let TypeName::Closure(type_name_closure) = &type_name else {
+            panic!("Expected a closure type name, but got: {type_name:?}");
+        };
+        assert!(matches!(
+            type_name_closure.parent_path(),
+            [TypeName::QualifiedPath(_)]
+        ));
+        assert_eq!(type_name_qualified, type_name.to_full_string());
+        assert_eq!(
+            "{closure in <TypeParamSingle<Simple> as ClosureInTraitImpl>::closure_in_trait_method}",
+            type_name.as_str()
+        );
+    }
+
+    #[test]
+    fn parse_closure_in_generic_trait_impl_method() {
+        let type_name = TypeName::parse_strict(
+            "<my_crate::Foo<u8> as my_crate::Tr>::run<u16>::{{closure}}::{{closure}}",
+        )
+        .expect("Expected closure type name to parse.");
+
+        assert_eq!("{closure in <Foo<u8> as Tr>::run}", type_name.as_str());
+    }
+
+    #[test]
+    fn parse_qualified_path() {
+        let expected = TypeName::QualifiedPath(TypeNameQualifiedPath {
+            self_type: Box::new(TypeName::Struct(TypeNameStruct {
+                module_path: vec!["alloc".into(), "vec".into()],
+                simple_name: "Vec".into(),
+                type_params: vec![GenericArg::Type(TypeName::Struct(TypeNameStruct {
+                    module_path: vec![],
+                    simple_name: "u8".into(),
+                    type_params: vec![],
+                }))],
+            })),
+            trait_path: Some(TypeNameStruct {
+                module_path: vec!["core".into(), "iter".into()],
+                simple_name: "IntoIterator".into(),
+                type_params: vec![],
+            }),
+            assoc_path: vec!["IntoIter".into()],
+        });
+
+        let actual = TypeName::from("<alloc::vec::Vec<u8> as core::iter::IntoIterator>::IntoIter");
+
+        assert_eq!(expected, actual);
+        assert_eq!("<Vec<u8> as IntoIterator>::IntoIter", actual.as_str());
+    }
+
+    #[test]
+    fn parse_qualified_path_mn() {
+        let type_name = TypeName::from(
+            "<alloc::vec::Vec<alloc::string::String> as core::iter::IntoIterator>::IntoIter",
+        );
+
+        assert_eq!(
+            "<alloc::..::Vec<alloc::..::String> as core::..::IntoIterator>::IntoIter",
+            type_name.as_str_mn(1, 0)
+        );
+    }
+
+    #[test]
+    fn parse_qualified_path_without_trait() {
+        let type_name = TypeName::from("core::option::Option<<my_crate::Foo>::Assoc>");
+
+        assert_eq!("Option<<Foo>::Assoc>", type_name.as_str());
+    }
+
+    #[test]
+    fn parse_trait_bounds() {
+        let expected = TypeName::Reference(TypeNameReference {
+            lifetime: None,
+            mutable: false,
+            type_param: Box::new(TypeName::Trait(TypeNameTrait {
+                bounds: vec![
+                    TypeNameTraitBound {
+                        bound_lifetimes: vec![],
+                        inner: TypeNameStruct {
+                            module_path: vec!["my_crate".into()],
+                            simple_name: "MyTrait".into(),
+                            type_params: vec![],
+                        },
+                        fn_params: None,
+                        fn_return_type: None,
+                    },
+                    TypeNameTraitBound {
+                        bound_lifetimes: vec![],
+                        inner: TypeNameStruct {
+                            module_path: vec!["core".into(), "marker".into()],
+                            simple_name: "Send".into(),
+                            type_params: vec![],
+                        },
+                        fn_params: None,
+                        fn_return_type: None,
+                    },
+                ],
+                lifetime_bounds: vec!["'static".into()],
+                parenthesized: true,
+            })),
+        });
+
+        let actual = TypeName::from("&(dyn my_crate::MyTrait + core::marker::Send + 'static)");
+
+        assert_eq!(expected, actual);
+        assert_eq!("&(dyn MyTrait + Send + 'static)", actual.as_str());
+    }
+
+    #[test]
+    fn parse_lifetime_only_trait_object_as_unknown() {
+        // Trait objects need a trait bound, so `dyn 'static` is not parsed as
+        // one.
+        let type_name = TypeName::from("alloc::boxed::Box<dyn 'static>");
+
+        assert_eq!(
+            TypeName::Struct(TypeNameStruct {
+                module_path: vec!["alloc".into(), "boxed".into()],
+                simple_name: "Box".into(),
+                type_params: vec![GenericArg::Type(TypeName::Unknown("dyn 'static".into()))],
+            }),
+            type_name
+        );
+        assert_eq!("Box<dyn 'static>", type_name.as_str());
+        assert_eq!(
+            TypeName::Unknown("dyn 'static".into()),
+            TypeName::from("dyn 'static")
+        );
+        assert!(TypeName::parse_strict("dyn 'static").is_err());
+    }
+
+    #[test]
+    fn render_trait_object_without_trait_bounds() {
+        let type_name = TypeName::Trait(TypeNameTrait {
+            bounds: vec![],
+            lifetime_bounds: vec!["'static".into()],
+            parenthesized: false,
+        });
+
+        assert_eq!("dyn 'static", type_name.as_str());
+        match &type_name {
+            TypeName::Trait(type_name_trait) => {
+                assert!(type_name_trait.module_path().is_empty());
+                assert_eq!("", type_name_trait.simple_name());
+                assert!(type_name_trait.type_params().is_empty());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn parse_trait_in_tuple() {
+        let type_name = TypeName::from("(&dyn core::fmt::Debug + core::marker::Send, u32)");
+
+        assert_eq!("(&dyn Debug + Send, u32)", type_name.as_str());
+    }
+
+    #[test]
+    fn parse_generic_arg_binding() {
+        let expected = TypeName::Trait(TypeNameTrait {
+            bounds: vec![TypeNameTraitBound {
+                bound_lifetimes: vec![],
+                inner: TypeNameStruct {
+                    module_path: vec![
+                        "core".into(),
+                        "iter".into(),
+                        "traits".into(),
+                        "iterator".into(),
+                    ],
+                    simple_name: "Iterator".into(),
+                    type_params: vec![GenericArg::Binding(TypeNameBinding {
+                        name: "Item".into(),
+                        type_name: TypeName::Struct(TypeNameStruct {
+                            module_path: vec!["alloc".into(), "string".into()],
+                            simple_name: "String".into(),
+                            type_params: vec![],
+                        }),
+                    })],
+                },
+                fn_params: None,
+                fn_return_type: None,
+            }],
+            lifetime_bounds: vec![],
+            parenthesized: false,
+        });
+
+        let actual = TypeName::from(
+            "dyn core::iter::traits::iterator::Iterator<Item = alloc::string::String>",
+        );
+
+        assert_eq!(expected, actual);
+        assert_eq!("dyn Iterator<Item = String>", actual.as_str());
+    }
+
+    #[test]
+    fn parse_generic_arg_binding_raw_identifier() {
+        let expected = TypeName::Struct(TypeNameStruct {
+            module_path: vec!["a".into()],
+            simple_name: "Foo".into(),
+            type_params: vec![GenericArg::Binding(TypeNameBinding {
+                name: "r#type".into(),
+                type_name: TypeName::Struct(TypeNameStruct {
+                    module_path: vec![],
+                    simple_name: "u8".into(),
+                    type_params: vec![],
+                }),
+            })],
+        });
+
+        let actual = TypeName::from("a::Foo<r#type = u8>");
+
+        assert_eq!(expected, actual);
+        assert_eq!("Foo<r#type = u8>", actual.as_str());
+    }
+
+    #[test]
+    fn parse_generic_arg_lifetime_and_const() {
+        let expected = TypeName::Struct(TypeNameStruct {
+            module_path: vec!["my_crate".into()],
+            simple_name: "Buffer".into(),
+            type_params: vec![
+                GenericArg::Lifetime("'_".into()),
+                GenericArg::Type(TypeName::Struct(TypeNameStruct {
+                    module_path: vec![],
+                    simple_name: "u8".into(),
+                    type_params: vec![],
+                })),
+                GenericArg::Const("16".into()),
+            ],
+        });
+
+        let actual = TypeName::from("my_crate::Buffer<'_, u8, 16>");
+
+        assert_eq!(expected, actual);
+        assert_eq!("Buffer<'_, u8, 16>", actual.as_str());
+    }
+
+    #[test]
+    fn parse_generic_arg_const_block() {
+        let expected = TypeName::Struct(TypeNameStruct {
+            module_path: vec!["my_crate".into()],
+            simple_name: "Buffer".into(),
+            type_params: vec![
+                GenericArg::Const("{ N + 1 }".into()),
+                GenericArg::Const("'\\u{1f980}'".into()),
+            ],
+        });
+
+        let actual = TypeName::from("my_crate::Buffer<{ N + 1 }, '\\u{1f980}'>");
+
+        assert_eq!(expected, actual);
+        assert_eq!("Buffer<{ N + 1 }, '\\u{1f980}'>", actual.as_str());
+    }
+
+    #[test]
+    fn parse_raw_identifiers() {
+        let expected = TypeName::Struct(TypeNameStruct {
+            module_path: vec!["my_crate".into(), "r#mod".into()],
+            simple_name: "r#type".into(),
+            type_params: vec![GenericArg::Type(TypeName::Struct(TypeNameStruct {
+                module_path: vec!["FFI".into()],
+                simple_name: "handle".into(),
+                type_params: vec![],
+            }))],
+        });
+
+        let actual = TypeName::from("my_crate::r#mod::r#type<FFI::handle>");
+
+        assert_eq!(expected, actual);
+        assert_eq!("r#type<handle>", actual.as_str());
+    }
+
+    #[test]
+    fn parse_trait_fn_sugar() {
+        let expected = TypeName::Trait(TypeNameTrait {
+            bounds: vec![TypeNameTraitBound {
+                bound_lifetimes: vec!["'a".into()],
+                inner: TypeNameStruct {
+                    module_path: vec!["core".into(), "ops".into(), "function".into()],
+                    simple_name: "Fn".into(),
+                    type_params: vec![],
+                },
+                fn_params: Some(vec![TypeName::Reference(TypeNameReference {
+                    lifetime: Some("'a".into()),
+                    mutable: false,
+                    type_param: Box::new(TypeName::Struct(TypeNameStruct {
+                        module_path: vec![],
+                        simple_name: "str".into(),
+                        type_params: vec![],
+                    })),
+                })]),
+                fn_return_type: Some(Box::new(TypeName::Reference(TypeNameReference {
+                    lifetime: Some("'a".into()),
+                    mutable: false,
+                    type_param: Box::new(TypeName::Struct(TypeNameStruct {
+                        module_path: vec![],
+                        simple_name: "str".into(),
+                        type_params: vec![],
+                    })),
+                }))),
+            }],
+            lifetime_bounds: vec![],
+            parenthesized: false,
+        });
+
+        let actual = TypeName::from("dyn for<'a> core::ops::function::Fn(&'a str) -> &'a str");
+
+        assert_eq!(expected, actual);
+        assert_eq!("dyn for<'a> Fn(&'a str) -> &'a str", actual.as_str());
+    }
+
+    #[test]
+    fn parse_function_for_lifetimes() {
+        let type_name = TypeName::from("for<'a, 'b> fn(&'a u8, &'b u8) -> &'a u8");
+
+        assert_eq!(
+            "for<'a, 'b> fn(&'a u8, &'b u8) -> &'a u8",
+            type_name.as_str()
+        );
+    }
+
+    #[test]
+    fn parse_returns_error_for_malformed_input() {
+        assert_eq!(
+            Err(ParseError {
+                offset: 0,
+                kind: ParseErrorKind::UnexpectedChar('%'),
+            }),
+            TypeName::parse("%")
+        );
+        assert_eq!(
+            Err(ParseError {
+                offset: 8,
+                kind: ParseErrorKind::UnexpectedChar('%'),
+            }),
+            TypeName::parse("&'a mut %")
+        );
+        assert_eq!(
+            Err(ParseError {
+                offset: 6,
+                kind: ParseErrorKind::UnexpectedEnd,
+            }),
+            TypeName::parse("*const")
+        );
+    }
+
+    #[test]
+    fn parse_strict_returns_error_for_trailing_input() {
+        let error = TypeName::parse_strict("alloc::vec::Vec<u8> extra").unwrap_err();
+
+        assert_eq!(
+            ParseError {
+                offset: 19,
+                kind: ParseErrorKind::TrailingInput { len: 6 },
+            },
+            error
+        );
+        assert_eq!(19..25, error.span());
+        assert_eq!(
+            Ok(TypeName::from("alloc::vec::Vec<u8>")),
+            TypeName::parse_strict("alloc::vec::Vec<u8>")
+        );
+    }
+
+    #[test]
+    fn parse_unknown_generic_args_tuple_fields_and_fn_params() {
+        let type_name = TypeName::from(
+            "std::collections::hash::map::HashMap<my_crate::Key @ 1, alloc::vec::Vec<%future<syntax>%>>",
+        );
+        assert_eq!(
+            "HashMap<my_crate::Key @ 1, Vec<%future<syntax>%>>",
+            type_name.as_str()
+        );
+
+        let type_name = TypeName::from("my_crate::Foo<#'>', u8>");
+        assert_eq!("Foo<#'>', u8>", type_name.as_str());
+
+        let type_name = TypeName::from("(u8, ~weird, alloc::string::String)");
+        assert_eq!("(u8, ~weird, String)", type_name.as_str());
+
+        let type_name = TypeName::from("fn(~a -> b, [~c; 3]) -> alloc::string::String");
+        assert_eq!("fn(~a -> b, [~c; 3]) -> String", type_name.as_str());
+    }
+
+    #[test]
+    fn parse_unknown_whole_input_when_not_fully_parsed() {
+        assert_eq!(TypeName::Unknown("%%".into()), TypeName::from("%%"));
+        assert_eq!(
+            TypeName::Unknown("my_crate::Foo<u8".into()),
+            TypeName::from("my_crate::Foo<u8")
+        );
+    }
+
+    #[test]
+    fn to_full_string_round_trips_closures_and_unknown() {
+        [
+            "{async fn body of my_crate::run()}",
+            "my_crate::main::{closure#0}::{closure#1}",
+            "core::option::Option<my_crate::main::{{closure}}>",
+            "alloc::vec::Vec<my_crate::Key @ 1>",
+            "",
+        ]
+        .iter()
+        .for_each(|std_type_name| {
+            assert_eq!(
+                *std_type_name,
+                TypeName::from(*std_type_name).to_full_string()
+            );
+        });
+    }
+
+    #[test]
+    fn empty_tuple_renders_as_unit() {
+        let type_name = TypeName::Tuple(TypeNameTuple {
+            type_params: Vec::new(),
+        });
+
+        assert_eq!("()", type_name.as_str());
+    }
+
+    #[test]
+    fn into_owned_outlives_source_string() {
+        let std_type_name = String::from(
+            "alloc::boxed::Box<dyn core::ops::function::Fn(&'_ str) -> [u8; 3] + core::marker::Send>",
+        );
+        let type_name = TypeName::from(std_type_name.as_str());
+        let type_name_owned = type_name.clone().into_owned();
+        drop(std_type_name);
+
+        assert_eq!(
+            "Box<dyn Fn(&'_ str) -> [u8; 3] + Send>",
+            type_name_owned.as_str()
+        );
+        assert_eq!(
+            "alloc::boxed::Box<dyn core::ops::function::Fn(&'_ str) -> [u8; 3] + core::marker::Send>",
+            type_name_owned.to_full_string()
+        );
+    }
+
+    #[test]
+    fn parse_elides_types_nested_deeper_than_max_depth() {
+        let std_type_name = "core::result::Result<alloc::vec::Vec<alloc::vec::Vec<u8>>, \
+            (u8, &'_ [alloc::string::String])>";
+        let limits = ParseLimits::new().max_depth(2);
+        let type_name = TypeName::parse_strict_with_limits(std_type_name, &limits).unwrap();
+
+        assert_eq!("Result<Vec<..>, (.., ..)>", type_name.as_str());
+        assert_eq!(std_type_name, type_name.to_full_string());
+    }
+
+    #[test]
+    fn parse_elides_types_beginning_after_max_len() {
+        let std_type_name = "(alloc::string::String, u8, alloc::vec::Vec<u8>)";
+        let limits = ParseLimits::new().max_len(25);
+        let type_name = TypeName::parse_strict_with_limits(std_type_name, &limits).unwrap();
+
+        assert_eq!("(String, u8, ..)", type_name.as_str());
+        assert_eq!(std_type_name, type_name.to_full_string());
+    }
+
+    #[test]
+    fn parse_deeply_nested_type_does_not_overflow_stack() {
+        let depth = 10_000;
+        let std_type_name = format!(
+            "{}u8{}",
+            "core::iter::adapters::map::Map<".repeat(depth),
+            ">".repeat(depth)
+        );
+
+        let type_name = TypeName::from(std_type_name.as_str());
+
+        let max_depth = ParseLimits::DEFAULT_MAX_DEPTH;
+        let expected = format!("{}..{}", "Map<".repeat(max_depth), ">".repeat(max_depth));
+        assert_eq!(expected, type_name.as_str());
+        assert_eq!(std_type_name, type_name.to_full_string());
+    }
+
+    #[test]
+    fn from_str_parses_strictly_into_owned() {
+        let type_name = "core::option::Option<alloc::string::String>"
+            .parse::<TypeName<'static>>()
+            .unwrap();
+        assert_eq!("Option<String>", type_name.as_str());
+
+        assert_eq!(
+            Err(ParseError {
+                offset: 13,
+                kind: ParseErrorKind::TrailingInput { len: 3 },
+            }),
+            "my_crate::Foo<u8".parse::<TypeName<'static>>()
+        );
+    }
+
+    #[test]
+    fn into_display_owns_type_name() {
+        let display = {
+            let std_type_name =
+                String::from("alloc::boxed::Box<dyn core::error::Error + core::marker::Send>");
+            TypeName::from(std_type_name.as_str())
+                .into_owned()
+                .into_display_mn_opts(0, 1, TypeParamsFmtOpts::All)
+                .elide_auto_traits(true)
+        };
+
+        assert_eq!("..::boxed::Box<dyn ..::error::Error>", format!("{display}"));
+    }
+
+    #[test]
+    fn rendered_len_matches_written_string() {
+        let type_name = TypeName::from(
+            "core::result::Result<alloc::vec::Vec<(u8, &'_ str)>, alloc::boxed::Box<dyn \
+            core::error::Error + core::marker::Send>>",
+        );
+
+        [(0, 0), (1, 0), (0, 1), (1, 1), (usize::MAX, 0)]
+            .iter()
+            .for_each(|&(m, n)| {
+                [TypeParamsFmtOpts::All, TypeParamsFmtOpts::Std]
+                    .iter()
+                    .for_each(|&type_params_fmt_opts| {
+                        let rendered = type_name.as_str_mn_opts(m, n, type_params_fmt_opts);
+
+                        assert_eq!(
+                            rendered.len(),
+                            type_name.rendered_len_opts(m, n, type_params_fmt_opts)
+                        );
+                        assert_eq!(rendered.len(), rendered.capacity());
+                    });
+            });
+    }
+
+    #[test]
+    fn normalize_rewrites_core_and_alloc_paths_to_std() {
+        let type_name_qualified = "<alloc::vec::Vec<core::option::Option<u8>> as \
+            core::iter::traits::collect::IntoIterator>::IntoIter";
+        let mut type_name = TypeName::from(type_name_qualified);
+        type_name.normalize();
+
+        assert_eq!(
+            "<std::vec::Vec<std::option::Option<u8>> as \
+            std::iter::traits::collect::IntoIterator>::IntoIter",
+            type_name.to_full_string()
+        );
+
+        let fmt = TypeNameFmt::full().normalize_std_paths(true);
+        assert_eq!(
+            type_name.to_full_string(),
+            TypeName::from(type_name_qualified).as_str_with(&fmt)
+        );
+    }
+
+    #[test]
+    fn normalize_keeps_other_crate_paths() {
+        let mut type_name = TypeName::from(
+            "&dyn core::ops::function::Fn(my_crate::core::Key) -> \
+            alloc::boxed::Box<my_alloc::Vec<[core::primitive::u8; 3]>>",
+        );
+        type_name.normalize();
+
+        assert_eq!(
+            "&dyn std::ops::function::Fn(my_crate::core::Key) -> \
+            std::boxed::Box<my_alloc::Vec<[std::primitive::u8; 3]>>",
+            type_name.to_full_string()
+        );
+    }
+
     struct Simple;
     struct TypeParamSingle<T>(T);
     struct TypeParamDouble<T, U>(T, U);
+
+    fn closure_in_generic_fn<T>(_: T) -> &'static str {
+        core::any::type_name_of_val(&|| ())
+    }
+
+    impl<T> TypeParamSingle<T> {
+        fn closure_in_method<U>(_: U) -> &'static str {
+            core::any::type_name_of_val(&|| ())
+        }
+    }
+
+    trait ClosureInTraitImpl {
+        fn closure_in_trait_method() -> &'static str;
+    }
+
+    impl ClosureInTraitImpl for TypeParamSingle<Simple> {
+        fn closure_in_trait_method() -> &'static str {
+            core::any::type_name_of_val(&|| ())
+        }
+    }
 }